@@ -0,0 +1,232 @@
+use crate::{code_block::*, token_helpers::*};
+
+/// How close the values of an `$approx` assertion have to be, chosen by the word
+/// (if any) that starts the `$within` tolerance.
+#[derive(Clone, Copy)]
+pub(crate) enum Tolerance {
+    /// `$within 1e-5`, the difference is at most the tolerance.
+    Absolute,
+    /// `$within relative 1e-5`, the difference is at most the tolerance scaled by the
+    /// larger of the two values.
+    Relative,
+    /// `$within ulps 4`, the values are at most that many representable floats apart.
+    Ulps,
+}
+
+impl Tolerance {
+    /// Splits the kind of tolerance from the tokens of its value.
+    pub(crate) fn from_tokens(mut tokens: CodeBlock) -> (Self, CodeBlock) {
+        let kind = match tokens.first() {
+            Some(TokenTree::Ident(ident)) if tokens.len() > 1 && *ident == "relative" => {
+                Tolerance::Relative
+            }
+            Some(TokenTree::Ident(ident)) if tokens.len() > 1 && *ident == "ulps" => {
+                Tolerance::Ulps
+            }
+            _ => return (Tolerance::Absolute, tokens),
+        };
+
+        tokens.remove(0);
+        (kind, tokens)
+    }
+
+    /// `spoke_approx::Tolerance::absolute(value)` or its equivalent.
+    pub(crate) fn generate(&self, value: CodeBlock, sp: Span) -> CodeBlock {
+        let constructor = match self {
+            Tolerance::Absolute => "absolute",
+            Tolerance::Relative => "relative",
+            Tolerance::Ulps => "ulps",
+        };
+
+        let mut output: CodeBlock = code("spoke_approx::Tolerance::", sp).into_iter().collect();
+        output.extend([ident(constructor, sp), parenthesised(value, sp)]);
+        output
+    }
+}
+
+/// The helpers that `$approx` assertions call at runtime, generated once into any
+/// suite that uses them. The traits let a single float, an array, a slice or a `Vec`
+/// be compared in the same way, while keeping `f32` and `f64` apart so that the
+/// distance in ulps is measured in the type being tested.
+pub(crate) fn support_module(sp: Span) -> TokenStream {
+    code(SUPPORT_MODULE, sp)
+}
+
+const SUPPORT_MODULE: &str = r#"
+#[allow(dead_code)]
+mod spoke_approx {
+    pub enum Tolerance {
+        Absolute(f64),
+        Relative(f64),
+        Ulps(u64),
+    }
+
+    impl Tolerance {
+        pub fn absolute(tolerance: impl ::std::convert::Into<f64>) -> Self {
+            Tolerance::Absolute(tolerance.into())
+        }
+
+        pub fn relative(tolerance: impl ::std::convert::Into<f64>) -> Self {
+            Tolerance::Relative(tolerance.into())
+        }
+
+        pub fn ulps(tolerance: u64) -> Self {
+            Tolerance::Ulps(tolerance)
+        }
+
+        fn allows<F: Float>(&self, actual: F, expected: F) -> bool {
+            if actual.is_nan() || expected.is_nan() {
+                return false;
+            }
+            if actual == expected {
+                return true;
+            }
+            match *self {
+                Tolerance::Absolute(tolerance) => actual.distance(expected) <= tolerance,
+                Tolerance::Relative(tolerance) => {
+                    actual.distance(expected)
+                        <= tolerance * actual.magnitude().max(expected.magnitude())
+                }
+                Tolerance::Ulps(tolerance) => actual.ulps(expected) <= tolerance,
+            }
+        }
+    }
+
+    impl ::std::fmt::Display for Tolerance {
+        fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+            match self {
+                Tolerance::Absolute(tolerance) => write!(f, "absolute tolerance {:?}", tolerance),
+                Tolerance::Relative(tolerance) => write!(f, "relative tolerance {:?}", tolerance),
+                Tolerance::Ulps(tolerance) => write!(f, "tolerance of {} ulps", tolerance),
+            }
+        }
+    }
+
+    pub trait Float: Copy + PartialEq + ::std::fmt::Debug {
+        fn distance(self, other: Self) -> f64;
+        fn magnitude(self) -> f64;
+        fn ulps(self, other: Self) -> u64;
+        fn is_nan(self) -> bool;
+    }
+
+    impl Float for f64 {
+        fn distance(self, other: Self) -> f64 {
+            (self - other).abs()
+        }
+        fn magnitude(self) -> f64 {
+            self.abs()
+        }
+        fn ulps(self, other: Self) -> u64 {
+            let ordered = |value: f64| {
+                let bits = value.to_bits() as i64;
+                if bits < 0 { i64::MIN - bits } else { bits }
+            };
+            ordered(self).abs_diff(ordered(other))
+        }
+        fn is_nan(self) -> bool {
+            f64::is_nan(self)
+        }
+    }
+
+    impl Float for f32 {
+        fn distance(self, other: Self) -> f64 {
+            (self as f64 - other as f64).abs()
+        }
+        fn magnitude(self) -> f64 {
+            (self as f64).abs()
+        }
+        fn ulps(self, other: Self) -> u64 {
+            let ordered = |value: f32| {
+                let bits = value.to_bits() as i32;
+                if bits < 0 { i32::MIN - bits } else { bits }
+            };
+            ordered(self).abs_diff(ordered(other)) as u64
+        }
+        fn is_nan(self) -> bool {
+            f32::is_nan(self)
+        }
+    }
+
+    pub trait Floats<F> {
+        fn floats(&self) -> &[F];
+        fn is_collection(&self) -> bool {
+            true
+        }
+    }
+
+    impl Floats<f64> for f64 {
+        fn floats(&self) -> &[f64] {
+            ::std::slice::from_ref(self)
+        }
+        fn is_collection(&self) -> bool {
+            false
+        }
+    }
+
+    impl Floats<f32> for f32 {
+        fn floats(&self) -> &[f32] {
+            ::std::slice::from_ref(self)
+        }
+        fn is_collection(&self) -> bool {
+            false
+        }
+    }
+
+    impl<F: Float, const N: usize> Floats<F> for [F; N] {
+        fn floats(&self) -> &[F] {
+            self
+        }
+    }
+
+    impl<F: Float> Floats<F> for [F] {
+        fn floats(&self) -> &[F] {
+            self
+        }
+    }
+
+    impl<F: Float> Floats<F> for ::std::vec::Vec<F> {
+        fn floats(&self) -> &[F] {
+            self
+        }
+    }
+
+    impl<F: Float, T: Floats<F> + ?Sized> Floats<F> for &T {
+        fn floats(&self) -> &[F] {
+            (**self).floats()
+        }
+        fn is_collection(&self) -> bool {
+            (**self).is_collection()
+        }
+    }
+
+    /// Describes the first value outside the tolerance, if there is one.
+    pub fn check<F: Float>(
+        actual: &(impl Floats<F> + ?Sized),
+        expected: &(impl Floats<F> + ?Sized),
+        tolerance: Tolerance,
+    ) -> ::std::option::Option<::std::string::String> {
+        let (left, right) = (actual.floats(), expected.floats());
+        if left.len() != right.len() {
+            return ::std::option::Option::Some(format!(
+                "the lengths differ\n  actual: {} values\nexpected: {} values",
+                left.len(),
+                right.len()
+            ));
+        }
+
+        let index = left
+            .iter()
+            .zip(right)
+            .position(|(actual, expected)| !tolerance.allows(*actual, *expected))?;
+
+        let location = match actual.is_collection() || expected.is_collection() {
+            true => format!(" at index {}", index),
+            false => ::std::string::String::new(),
+        };
+        ::std::option::Option::Some(format!(
+            "the values differ{} by more than the {}\n  actual: {:?}\nexpected: {:?}",
+            location, tolerance, left[index], right[index]
+        ))
+    }
+}
+"#;
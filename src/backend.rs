@@ -0,0 +1,244 @@
+use crate::{code_block::*, token_helpers::*};
+
+/// The assertion macros that the generated tests are written with, every assertion
+/// goes through here so the choice is made in one place.
+#[derive(Clone, Copy)]
+pub(crate) enum Backend {
+    /// `assert!`, `assert_eq!` and `assert_ne!` from the standard library.
+    Std,
+    /// `assert2::assert!` which reports the values of each side of a comparison.
+    Assert2,
+    /// The standard library, except that equality is checked by `spoke_support` so
+    /// that large values are reported as a diff.
+    Diff,
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Comparison {
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            Comparison::Eq | Comparison::Ne => "equality",
+            _ => "ordering",
+        }
+    }
+
+    /// The standard library only has macros for equality, the orderings are
+    /// spelled out so that they report their operands in the same way.
+    fn std_macro(&self) -> Option<&'static str> {
+        match self {
+            Comparison::Eq => Some("assert_eq"),
+            Comparison::Ne => Some("assert_ne"),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn operator(&self) -> &'static str {
+        match self {
+            Comparison::Eq => "==",
+            Comparison::Ne => "!=",
+            Comparison::Lt => "<",
+            Comparison::Le => "<=",
+            Comparison::Gt => ">",
+            Comparison::Ge => ">=",
+        }
+    }
+}
+
+impl Backend {
+    /// The backend chosen by the crate features. The unit tests are written against
+    /// the standard macros whatever the features, and pick other backends themselves.
+    pub(crate) fn selected() -> Self {
+        if cfg!(test) {
+            Backend::Std
+        } else {
+            Self::from_features(cfg!(feature = "assert2"), cfg!(feature = "diff"))
+        }
+    }
+
+    /// The backend for the enabled features, assert2 is used when both are enabled.
+    pub(crate) fn from_features(assert2: bool, diff: bool) -> Self {
+        match (assert2, diff) {
+            (true, _) => Backend::Assert2,
+            (false, true) => Backend::Diff,
+            (false, false) => Backend::Std,
+        }
+    }
+
+    /// Whether the assertion macro reports the parts of a failing expression itself,
+    /// otherwise plain assertions are taken apart to report them.
+    pub(crate) fn reports_expressions(&self) -> bool {
+        matches!(self, Backend::Assert2)
+    }
+
+    /// `assert!(condition, message...)`, the message must start with its comma.
+    pub(crate) fn assert(
+        &self,
+        condition: impl IterableTokens,
+        message: impl IterableTokens,
+        sp: Span,
+    ) -> CodeBlock {
+        let mut output = self.assert_macro(sp);
+        output.extend([
+            parenthesised(condition.into_iter().chain(message), sp),
+            punct(';', sp),
+        ]);
+        output
+    }
+
+    /// `assert_eq!(left, right, message...)` or its equivalent, the message must
+    /// start with its comma.
+    pub(crate) fn compare(
+        &self,
+        left: impl IterableTokens,
+        comparison: Comparison,
+        right: impl IterableTokens,
+        message: impl IterableTokens,
+        sp: Span,
+    ) -> CodeBlock {
+        match (self, comparison.std_macro()) {
+            (Backend::Diff, _) if matches!(comparison, Comparison::Eq) => {
+                self.diff_eq(left, right, message, sp)
+            }
+            (Backend::Std | Backend::Diff, Some(std_macro)) => vec![
+                ident(std_macro, sp),
+                punct('!', sp),
+                parenthesised(
+                    left.into_iter()
+                        .chain([punct(',', sp)])
+                        .chain(right)
+                        .chain(message),
+                    sp,
+                ),
+                punct(';', sp),
+            ],
+            (Backend::Std | Backend::Diff, None) => {
+                self.std_ordering(left, comparison, right, message, sp)
+            }
+            (Backend::Assert2, _) => {
+                // invisible groups keep each side's precedence without adding
+                // parentheses to the expression assert2 prints
+                let mut condition = vec![invisible(left, sp)];
+                condition.extend(code(comparison.operator(), sp));
+                condition.push(invisible(right, sp));
+                self.assert(condition, message, sp)
+            }
+        }
+    }
+
+    /// `match (&left, &right) { (l, r) => assert!(*l < *r, ...) }` reporting the
+    /// operands in the same format as `assert_eq!`.
+    fn std_ordering(
+        &self,
+        left: impl IterableTokens,
+        comparison: Comparison,
+        right: impl IterableTokens,
+        message: impl IterableTokens,
+        sp: Span,
+    ) -> CodeBlock {
+        let operator = comparison.operator();
+        let mut message = message.into_iter();
+
+        let mut report = match message.next() {
+            None => vec![lit_string(
+                &format!(
+                    "assertion `left {} right` failed\n  left: {{:?}}\n right: {{:?}}",
+                    operator
+                ),
+                sp,
+            )],
+            Some(_comma) => vec![
+                lit_string(
+                    &format!(
+                        "assertion `left {} right` failed: {{}}\n  left: {{:?}}\n right: {{:?}}",
+                        operator
+                    ),
+                    sp,
+                ),
+                punct(',', sp),
+                ident("format_args", sp),
+                punct('!', sp),
+                parenthesised(message, sp),
+            ],
+        };
+        report.extend(code(", spoke_left, spoke_right", sp));
+
+        let mut condition = code("*spoke_left", sp);
+        condition.extend(code(operator, sp));
+        condition.extend(code("*spoke_right", sp));
+
+        let mut output: CodeBlock = code("match", sp).into_iter().collect();
+        output.push(parenthesised(
+            [punct('&', sp), parenthesised(left, sp), punct(',', sp)]
+                .into_iter()
+                .chain([punct('&', sp), parenthesised(right, sp)]),
+            sp,
+        ));
+        output.push(braced(
+            code("(spoke_left, spoke_right) =>", sp)
+                .into_iter()
+                .chain([braced(
+                    self.assert(condition, [punct(',', sp)].into_iter().chain(report), sp),
+                    sp,
+                )]),
+            sp,
+        ));
+        output
+    }
+
+    /// `spoke_support::assert_eq(&left, &right, message)` where the message is an
+    /// `Option` of the `format_args!` for the failure message.
+    fn diff_eq(
+        &self,
+        left: impl IterableTokens,
+        right: impl IterableTokens,
+        message: impl IterableTokens,
+        sp: Span,
+    ) -> CodeBlock {
+        let mut message = message.into_iter().peekable();
+
+        let message = match message.peek() {
+            None => code("::std::option::Option::None", sp)
+                .into_iter()
+                .collect(),
+            Some(_) => {
+                let mut some: CodeBlock = code("::std::option::Option::Some", sp)
+                    .into_iter()
+                    .collect();
+                some.push(parenthesised(
+                    code("format_args!", sp)
+                        .into_iter()
+                        .chain([parenthesised(message.skip(1), sp)]),
+                    sp,
+                ));
+                some
+            }
+        };
+
+        let mut output: CodeBlock = code("::spoke_support::assert_eq", sp).into_iter().collect();
+        output.push(parenthesised(
+            [punct('&', sp), parenthesised(left, sp), punct(',', sp)]
+                .into_iter()
+                .chain([punct('&', sp), parenthesised(right, sp), punct(',', sp)])
+                .chain(message),
+            sp,
+        ));
+        output.push(punct(';', sp));
+        output
+    }
+
+    fn assert_macro(&self, sp: Span) -> CodeBlock {
+        match self {
+            Backend::Std | Backend::Diff => vec![ident("assert", sp), punct('!', sp)],
+            Backend::Assert2 => code("::assert2::assert!", sp).into_iter().collect(),
+        }
+    }
+}
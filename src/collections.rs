@@ -0,0 +1,107 @@
+use crate::token_helpers::*;
+
+/// The helpers that `$contains` and `$unordered_eq` assertions call at runtime,
+/// generated once into any suite that uses them. The collections are taken by value
+/// so that iterators such as `map.keys()` can be checked as well, with the items
+/// collected into a `Vec` for the report.
+pub(crate) fn support_module(sp: Span) -> TokenStream {
+    code(SUPPORT_MODULE, sp)
+}
+
+const SUPPORT_MODULE: &str = r#"
+#[allow(dead_code)]
+mod spoke_collections {
+    use ::std::{cell::Cell, fmt::Debug};
+
+    /// Holds the collection until it's known whether it is searched as text or as
+    /// a sequence of items, the method on `&Haystack` being chosen ahead of the one
+    /// on `&&Haystack` whenever the collection is a string.
+    pub struct Haystack<T>(Cell<Option<T>>);
+
+    impl<T> Haystack<T> {
+        pub fn new(haystack: T) -> Self {
+            Haystack(Cell::new(Some(haystack)))
+        }
+
+        fn take(&self) -> T {
+            self.0.take().expect("the haystack is only searched once")
+        }
+    }
+
+    pub trait ContainsText<N> {
+        fn spoke_contains(self, needle: N) -> Option<String>;
+    }
+
+    impl<T: AsRef<str>, N: AsRef<str>> ContainsText<N> for &Haystack<T> {
+        fn spoke_contains(self, needle: N) -> Option<String> {
+            let text = self.take();
+            let (text, needle) = (text.as_ref(), needle.as_ref());
+            match text.contains(needle) {
+                true => None,
+                false => Some(format!(
+                    "   missing: {:?}\n    string: {:?} ({} chars)",
+                    needle,
+                    text,
+                    text.chars().count()
+                )),
+            }
+        }
+    }
+
+    pub trait ContainsItem<N> {
+        fn spoke_contains(self, needle: N) -> Option<String>;
+    }
+
+    impl<T: IntoIterator, N: Debug> ContainsItem<N> for &&Haystack<T>
+    where
+        T::Item: PartialEq<N> + Debug,
+    {
+        fn spoke_contains(self, needle: N) -> Option<String> {
+            let items: Vec<T::Item> = self.take().into_iter().collect();
+            match items.iter().any(|item| *item == needle) {
+                true => None,
+                false => Some(format!(
+                    "   missing: {:?}\ncollection: {:?} ({} elements)",
+                    needle,
+                    items,
+                    items.len()
+                )),
+            }
+        }
+    }
+
+    /// Describes the items that are missing or extra, if there are any, where each
+    /// expected item accounts for at most one of the actual items.
+    pub fn unordered_eq<L, R>(actual: L, expected: R) -> Option<String>
+    where
+        L: IntoIterator,
+        R: IntoIterator,
+        L::Item: PartialEq<R::Item> + Debug,
+        R::Item: Debug,
+    {
+        let items: Vec<L::Item> = actual.into_iter().collect();
+        let mut missing: Vec<R::Item> = expected.into_iter().collect();
+        let expected_count = missing.len();
+        let mut extra = Vec::new();
+        for item in &items {
+            match missing.iter().position(|wanted| *item == *wanted) {
+                Some(index) => {
+                    missing.remove(index);
+                }
+                None => extra.push(item),
+            }
+        }
+        match missing.is_empty() && extra.is_empty() {
+            true => None,
+            false => Some(format!(
+                "   missing: {:?}\n     extra: {:?}\ncollection: {:?} ({} elements, expected {})",
+                missing,
+                extra,
+                items,
+                items.len(),
+                expected_count
+            )),
+        }
+    }
+}
+"#;
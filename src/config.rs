@@ -0,0 +1,128 @@
+use crate::{
+    code_block::CodeBlock,
+    name::{CompoundName, Name},
+    token_helpers::*,
+};
+
+/// Suite wide settings provided by `$config(...)` in the preamble.
+pub(crate) struct Config {
+    module: Option<Ident>,
+    import_super: Option<bool>,
+    async_runtime: Option<AsyncRuntime>,
+}
+
+/// How the tests inside an `$async` block are run.
+#[derive(Clone)]
+pub(crate) enum AsyncRuntime {
+    /// An attribute (such as `tokio::test`) which turns an `async fn` into a test.
+    Derive(CodeBlock),
+    /// A function (such as `smol::block_on`) which runs the test body to completion.
+    Function(CodeBlock),
+}
+
+pub(crate) enum ConfigOption {
+    Module,
+    Super,
+    Async,
+}
+
+impl ConfigOption {
+    pub(crate) fn list() -> String {
+        [
+            Self::Module.to_string(),
+            Self::Super.to_string(),
+            Self::Async.to_string(),
+        ]
+        .join(",")
+    }
+}
+
+impl std::fmt::Display for ConfigOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigOption::Module => write!(f, "module"),
+            ConfigOption::Super => write!(f, "super"),
+            ConfigOption::Async => write!(f, "async"),
+        }
+    }
+}
+
+pub(crate) fn config_option_match(ident: String) -> Result<ConfigOption, String> {
+    match ident.as_str() {
+        "module" => Result::Ok(ConfigOption::Module),
+        "super" => Result::Ok(ConfigOption::Super),
+        "async" => Result::Ok(ConfigOption::Async),
+        other => Result::Err(other.to_string()),
+    }
+}
+
+impl Config {
+    pub(crate) fn new() -> Self {
+        Self {
+            module: None,
+            import_super: None,
+            async_runtime: None,
+        }
+    }
+
+    pub(crate) fn is_set(&self, option: &ConfigOption) -> bool {
+        match option {
+            ConfigOption::Module => self.module.is_some(),
+            ConfigOption::Super => self.import_super.is_some(),
+            ConfigOption::Async => self.async_runtime.is_some(),
+        }
+    }
+
+    pub(crate) fn set_module(&mut self, module: Ident) {
+        self.module = Some(module);
+    }
+
+    pub(crate) fn set_import_super(&mut self, import_super: bool) {
+        self.import_super = Some(import_super);
+    }
+
+    pub(crate) fn set_async_runtime(&mut self, runtime: AsyncRuntime) {
+        self.async_runtime = Some(runtime);
+    }
+
+    /// Unless configured the async tests are generated as `#[tokio::test]`.
+    pub(crate) fn async_runtime(&self) -> AsyncRuntime {
+        self.async_runtime.clone().unwrap_or_else(|| {
+            AsyncRuntime::Derive(code("tokio::test", Span::call_site()).into_iter().collect())
+        })
+    }
+
+    /// Unless configured the module is named after the suite's first test and a hash
+    /// of the suite's code, so that several suites can live side by side in the same
+    /// scope without the macro having to know about the others. A suite without any
+    /// named tests only holds errors so it doesn't need a distinct name.
+    pub(crate) fn module(&self, suite_name: Option<&Name>, fingerprint: u32) -> Ident {
+        match (&self.module, suite_name) {
+            (Some(module), _) => module.clone(),
+            (None, Some(name)) => Ident::new(
+                &format!(
+                    "spoketest_{}_{:08x}",
+                    CompoundName::new().followed_by(name).function_name().0,
+                    fingerprint
+                ),
+                Span::call_site(),
+            ),
+            (None, None) => Ident::new("spoketest", Span::call_site()),
+        }
+    }
+
+    pub(crate) fn module_attributes(&self) -> TokenStream {
+        code(
+            "#[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]",
+            Span::call_site(),
+        )
+    }
+
+    pub(crate) fn imports(&self) -> TokenStream {
+        if self.import_super.unwrap_or(true) {
+            code("#[allow(unused_imports)] use super::*;", Span::call_site())
+        } else {
+            TokenStream::new()
+        }
+    }
+}
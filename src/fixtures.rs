@@ -0,0 +1,57 @@
+use crate::token_helpers::*;
+
+/// The registry of `$shared` fixtures and `$fork` states, generated once into any
+/// suite that uses them. The macro can't name the type of a fixture so it can't
+/// declare a `static` for each one, instead each fixture is numbered and its
+/// `OnceLock` is created on first use and leaked so that every test borrows the
+/// same value.
+pub(crate) fn support_module(sp: Span) -> TokenStream {
+    code(SUPPORT_MODULE, sp)
+}
+
+const SUPPORT_MODULE: &str = r#"
+#[allow(dead_code)]
+mod spoke_fixtures {
+    use ::std::{
+        any::Any,
+        sync::{Mutex, OnceLock},
+    };
+
+    static FIXTURES: Mutex<Vec<(usize, &'static (dyn Any + Send + Sync))>> =
+        Mutex::new(Vec::new());
+
+    /// The fixture's value, created by the first test to ask for it.
+    pub fn shared<T: Send + Sync + 'static>(fixture: usize, create: fn() -> T) -> &'static T {
+        cell(fixture).get_or_init(create)
+    }
+
+    /// A clone of the state of a `$fork` block, which is created by the first test
+    /// to ask for it. The state is only ever cloned so it doesn't need to be `Sync`.
+    pub fn forked<T: Clone + Send + 'static>(fixture: usize, create: fn() -> T) -> T {
+        cell::<Mutex<T>>(fixture)
+            .get_or_init(|| Mutex::new(create()))
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// The registry is only locked while finding the fixture so that other
+    /// fixtures can be created while this one is.
+    fn cell<T: Send + Sync + 'static>(fixture: usize) -> &'static OnceLock<T> {
+        let cell: &'static (dyn Any + Send + Sync) = {
+            let mut fixtures = FIXTURES.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            match fixtures.iter().find(|(number, _)| *number == fixture) {
+                Some((_, cell)) => *cell,
+                None => {
+                    let cell: &'static OnceLock<T> = Box::leak(Box::new(OnceLock::new()));
+                    fixtures.push((fixture, cell));
+                    cell
+                }
+            }
+        };
+
+        cell.downcast_ref::<OnceLock<T>>()
+            .expect("a fixture always has the same type")
+    }
+}
+"#;
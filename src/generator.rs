@@ -1,11 +1,36 @@
-use crate::{code_block::*, name::Name, parser::*, token_helpers::*};
+use crate::{
+    approx,
+    backend::Backend,
+    code_block::*,
+    collections,
+    config::{AsyncRuntime, Config, ConfigOption},
+    fixtures,
+    module_claims::{claim_module, collision},
+    name::Name,
+    parser::*,
+    power_assert, teardown,
+    token_helpers::*,
+    type_check,
+};
 use std::mem::take;
 
 pub(crate) struct SuiteGenerator {
     errors: Vec<CompilationError>,
     preamble: CodeBlock,
+    const_assertions: CodeBlock,
     tests: Vec<TestCase>,
     error_counter: usize,
+    config: Config,
+    suite_name: Option<Name>,
+    fingerprint: u32,
+    backend: Backend,
+    uses_approx: bool,
+    uses_collections: bool,
+    uses_power_assert: bool,
+    uses_types: bool,
+    uses_async_panics: bool,
+    shared_fixtures: usize,
+    preamble_fixtures: CodeBlock,
 }
 
 pub(crate) struct CompilationError {
@@ -17,6 +42,33 @@ pub(crate) struct TestCase {
     name: String,
     anchor: Span,
     code: Vec<TokenTree>,
+    teardowns: Vec<Teardown>,
+    invariants: CodeBlock,
+    ignored: Option<Ignored>,
+    pending: Option<Pending>,
+    asynchronous: Option<Asynchronous>,
+}
+
+struct Ignored {
+    location: Span,
+    reason: Option<Literal>,
+}
+
+struct Pending {
+    location: Span,
+    feature: Option<Literal>,
+}
+
+struct Asynchronous {
+    location: Span,
+    arguments: Option<Group>,
+}
+
+/// An `$after` block, run once the code pushed after it has finished.
+struct Teardown {
+    position: usize,
+    location: Span,
+    code: CodeBlock,
 }
 
 impl CompilationError {
@@ -33,8 +85,20 @@ impl SuiteGenerator {
         Self {
             errors: Vec::new(),
             preamble: CodeBlock::new(),
+            const_assertions: CodeBlock::new(),
             tests: Vec::new(),
             error_counter: 0,
+            config: Config::new(),
+            suite_name: None,
+            fingerprint: 0,
+            backend: Backend::selected(),
+            uses_approx: false,
+            uses_collections: false,
+            uses_power_assert: false,
+            uses_types: false,
+            uses_async_panics: false,
+            shared_fixtures: 0,
+            preamble_fixtures: CodeBlock::new(),
         }
     }
 
@@ -46,6 +110,80 @@ impl SuiteGenerator {
         self.tests.push(test);
     }
 
+    /// Compile time assertions are items of the module rather than tests.
+    pub(crate) fn push_const_assertion(&mut self, item: impl IterableTokens) {
+        self.const_assertions.extend(item);
+    }
+
+    pub(crate) fn has_tests(&self) -> bool {
+        !self.tests.is_empty() || !self.const_assertions.is_empty()
+    }
+
+    pub(crate) fn backend(&self) -> Backend {
+        self.backend
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_backend(&mut self, backend: Backend) {
+        self.backend = backend;
+    }
+
+    /// Asks for the helpers that `$approx` assertions call to be generated.
+    pub(crate) fn use_approx(&mut self) {
+        self.uses_approx = true;
+    }
+
+    /// Asks for the helpers that `$contains` and `$unordered_eq` call to be generated.
+    pub(crate) fn use_collections(&mut self) {
+        self.uses_collections = true;
+    }
+
+    /// Asks for the helpers that decomposed plain assertions call to be generated.
+    pub(crate) fn use_power_assert(&mut self) {
+        self.uses_power_assert = true;
+    }
+
+    /// Asks for the helper that `$type` assertions call to be generated.
+    pub(crate) fn use_types(&mut self) {
+        self.uses_types = true;
+    }
+
+    /// Asks for the wrapper that `$panics` assertions in async tests await to be generated.
+    pub(crate) fn use_async_panics(&mut self) {
+        self.uses_async_panics = true;
+    }
+
+    /// Numbers a new `$shared` fixture, asking for the registry of fixtures to be
+    /// generated.
+    pub(crate) fn number_shared_fixture(&mut self) -> usize {
+        self.shared_fixtures += 1;
+        self.shared_fixtures
+    }
+
+    /// The binding of a `$shared` fixture from the preamble, which starts every test.
+    pub(crate) fn push_shared_fixture(&mut self, binding: impl IterableTokens) {
+        self.preamble_fixtures.extend(binding);
+    }
+
+    pub(crate) fn config(&mut self) -> &mut Config {
+        &mut self.config
+    }
+
+    /// Remembers the first top level test name, which the module is named after.
+    pub(crate) fn name_suite(&mut self, name: &Name) {
+        if self.suite_name.is_none() {
+            self.suite_name = Some(name.clone());
+        }
+    }
+
+    /// A hash of the suite's code which keeps the module name of each suite distinct,
+    /// FNV-1a rather than the std hasher whose output may change between releases.
+    pub(crate) fn fingerprint_input(&mut self, input: &TokenStream) {
+        self.fingerprint = input.to_string().bytes().fold(0x811c_9dc5, |hash, byte| {
+            (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+        });
+    }
+
     pub(crate) fn push_error(&mut self, error: CompilationError) {
         self.errors.push(error);
     }
@@ -70,18 +208,69 @@ impl TestCase {
             name,
             anchor,
             code: CodeBlock::new(),
+            teardowns: Vec::new(),
+            invariants: CodeBlock::new(),
+            ignored: None,
+            pending: None,
+            asynchronous: None,
         }
     }
+
+    /// Runs the teardown code at the end of the test, even when the code pushed
+    /// from now on panics.
+    pub(crate) fn push_teardown(&mut self, location: &impl SpanSource, code: CodeBlock) {
+        self.teardowns.push(Teardown {
+            position: self.code.len(),
+            location: location.span(),
+            code,
+        });
+    }
+
+    /// Checks the invariants at the end of the test, after all of its own code.
+    pub(crate) fn push_invariant(&mut self, checks: impl IterableTokens) {
+        self.invariants.extend(checks)
+    }
+
+    /// Marks the test as ignored, the innermost (last) reason given is the one reported.
+    pub(crate) fn ignore(&mut self, location: &impl SpanSource, reason: Option<Literal>) {
+        self.ignored = Some(Ignored {
+            location: location.span(),
+            reason,
+        });
+    }
+
+    /// Marks the test as expected to fail, optionally only compiling it with the given feature.
+    pub(crate) fn pending(&mut self, location: &impl SpanSource, feature: Option<Literal>) {
+        self.pending = Some(Pending {
+            location: location.span(),
+            feature,
+        });
+    }
+
+    pub(crate) fn is_async(&self) -> bool {
+        self.asynchronous.is_some()
+    }
+
+    /// Generates the test as an async test, the innermost runtime arguments given are used.
+    pub(crate) fn make_async(&mut self, location: &impl SpanSource, arguments: Option<Group>) {
+        self.asynchronous = Some(Asynchronous {
+            location: location.span(),
+            arguments,
+        });
+    }
 }
 
 // ////////////////////////////////////////////////////////////////////////////
 
 impl SuiteGenerator {
     fn is_empty(&self) -> bool {
-        self.errors.is_empty() && self.preamble.is_empty() && self.tests.is_empty()
+        self.errors.is_empty()
+            && self.preamble.is_empty()
+            && self.const_assertions.is_empty()
+            && self.tests.is_empty()
     }
 
-    pub(crate) fn generate_output(self) -> TokenStream {
+    pub(crate) fn generate_output(mut self) -> TokenStream {
         let call_site = Span::call_site();
         let mut output = TokenStream::new();
 
@@ -89,49 +278,76 @@ impl SuiteGenerator {
             return output;
         }
 
+        let module = self.claim_module();
+
+        output.extend(self.config.module_attributes());
         output.extend([
-            punct('#', call_site),
-            bracketed(
-                [
-                    ident("cfg", call_site),
-                    parenthesised([ident("test", call_site)], call_site),
-                ],
-                call_site,
-            ),
-            punct('#', call_site),
-            bracketed(
-                [
-                    ident("allow", call_site),
-                    parenthesised([ident("unused_mut", call_site)], call_site),
-                ],
-                call_site,
-            ),
-            punct('#', call_site),
-            bracketed(
-                [
-                    ident("allow", call_site),
-                    parenthesised([ident("unused_variables", call_site)], call_site),
-                ],
-                call_site,
-            ),
             ident("mod", call_site),
-            ident("spoketest", call_site),
+            TokenTree::Ident(module),
             braced_stream(self.generate_suite()),
         ]);
 
         output
     }
 
+    /// A configured module name must be unique within the file, a derived name holds
+    /// a hash of the suite so it can't clash with another suite's.
+    fn claim_module(&mut self) -> Ident {
+        let module = self
+            .config
+            .module(self.suite_name.as_ref(), self.fingerprint);
+
+        if self.config.is_set(&ConfigOption::Module)
+            && let Err(claim) = claim_module(&module, self.fingerprint)
+        {
+            self.push_new_error(&module.span(), collision(&module, claim));
+        }
+
+        module
+    }
+
     fn generate_suite(mut self) -> TokenStream {
-        let mut output = TokenStream::new();
+        let mut output = self.config.imports();
         for error in self.errors {
             error.generate_into(&mut output)
         }
 
-        output.extend(take(&mut self.preamble).into_iter());
+        output.extend(take(&mut self.preamble));
+
+        if self.uses_approx {
+            output.extend(approx::support_module(Span::call_site()));
+        }
+
+        if self.uses_collections {
+            output.extend(collections::support_module(Span::call_site()));
+        }
+
+        if self.uses_power_assert {
+            output.extend(power_assert::support_module(Span::call_site()));
+        }
+
+        if self.uses_types {
+            output.extend(type_check::support_module(Span::call_site()));
+        }
+
+        if self.shared_fixtures > 0 {
+            output.extend(fixtures::support_module(Span::call_site()));
+        }
+
+        let runtime = self.config.async_runtime();
+        if self.uses_async_panics
+            || self
+                .tests
+                .iter()
+                .any(|test| test.catches_async_panics(&runtime))
+        {
+            output.extend(teardown::support_module(Span::call_site()));
+        }
+
+        output.extend(take(&mut self.const_assertions));
 
         for mut test in self.tests {
-            test.generate_into(&mut output)
+            test.generate_into(&mut output, &runtime, &self.preamble_fixtures)
         }
 
         output
@@ -139,16 +355,202 @@ impl SuiteGenerator {
 }
 
 impl TestCase {
-    fn generate_into(&mut self, output: &mut TokenStream) {
+    /// Whether the body of the async test function catches a panic, either to run its
+    /// teardown or because it is pending, which needs the `spoke_after` support module.
+    fn catches_async_panics(&self, runtime: &AsyncRuntime) -> bool {
+        let pending_async_fn = self.pending.is_some() && matches!(runtime, AsyncRuntime::Derive(_));
+        self.asynchronous.is_some() && (!self.teardowns.is_empty() || pending_async_fn)
+    }
+
+    fn generate_into(
+        &mut self,
+        output: &mut TokenStream,
+        runtime: &AsyncRuntime,
+        preamble_fixtures: &CodeBlock,
+    ) {
+        let mut code = take(&mut self.code);
+        code.append(&mut self.invariants);
+        let mut async_fn = None;
+
+        // the innermost teardown wraps the least code and so runs first
+        let asynchronous = self.asynchronous.is_some();
+        for teardown in take(&mut self.teardowns).into_iter().rev() {
+            let guarded = code.split_off(teardown.position);
+            code.extend(teardown.generate_around(guarded, asynchronous));
+        }
+        code.splice(0..0, preamble_fixtures.iter().cloned());
+
+        match (self.asynchronous.take(), runtime) {
+            (Some(asynchronous), AsyncRuntime::Derive(attribute)) => {
+                asynchronous.generate_attribute(attribute, output);
+                async_fn = Some(asynchronous.location);
+            }
+            (Some(asynchronous), AsyncRuntime::Function(function)) => {
+                code = asynchronous.generate_call(function, code, output);
+                self.generate_test_attribute(output);
+            }
+            (None, _) => self.generate_test_attribute(output),
+        }
+
+        if let Some(ignored) = self.ignored.take() {
+            ignored.generate_into(output);
+        }
+
+        if let Some(pending) = self.pending.take() {
+            code = pending.generate_into(code, async_fn.is_some(), output);
+        }
+
+        if let Some(location) = async_fn {
+            output.extend([ident("async", location)]);
+        }
+
         output.extend([
-            punct('#', self.anchor),
-            bracketed([ident("test", self.anchor)], self.anchor),
             ident("fn", self.anchor),
             ident(self.name.as_str(), self.anchor),
             parenthesised([], self.anchor),
-            braced(take(&mut self.code).into_iter(), self.anchor),
+            braced(code, self.anchor),
         ])
     }
+
+    fn generate_test_attribute(&self, output: &mut TokenStream) {
+        output.extend([
+            punct('#', self.anchor),
+            bracketed([ident("test", self.anchor)], self.anchor),
+        ]);
+    }
+}
+
+impl Ignored {
+    fn generate_into(self, output: &mut TokenStream) {
+        let span = self.location;
+        output.extend([
+            punct('#', span),
+            bracketed(
+                [ident("ignore", span)].into_iter().chain(
+                    self.reason
+                        .map(|reason| [punct('=', span), TokenTree::Literal(reason)])
+                        .into_iter()
+                        .flatten(),
+                ),
+                span,
+            ),
+        ]);
+    }
+}
+
+impl Asynchronous {
+    /// Emits the runtime's test attribute, any arguments given to the `$async` block
+    /// replace those given in the configuration.
+    fn generate_attribute(&self, attribute: &CodeBlock, output: &mut TokenStream) {
+        let span = self.location;
+        let mut attribute = attribute.clone();
+
+        if let Some(arguments) = &self.arguments {
+            if matches!(attribute.last(), Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis)
+            {
+                attribute.pop();
+            }
+            attribute.push(TokenTree::Group(arguments.clone()));
+        }
+
+        output.extend([punct('#', span), bracketed(attribute, span)]);
+    }
+
+    /// Returns the test code wrapped in an async block which is run to completion
+    /// by the runtime's function.
+    fn generate_call(
+        &self,
+        function: &CodeBlock,
+        test_code: CodeBlock,
+        output: &mut TokenStream,
+    ) -> CodeBlock {
+        let span = self.location;
+
+        if let Some(arguments) = &self.arguments {
+            CompilationError::new(
+                "arguments can't be given to `$async` when the runtime is configured with `async = fn = ...`",
+                &arguments.span(),
+            )
+            .generate_into(output);
+        }
+
+        let mut wrapped = function.clone();
+        wrapped.push(parenthesised(
+            [ident("async", span), braced(test_code, span)],
+            span,
+        ));
+        wrapped.push(punct(';', span));
+        wrapped
+    }
+}
+
+impl Pending {
+    /// Emits any feature gate for the test, returning the test code wrapped so that
+    /// the test only passes while the original code still fails.
+    fn generate_into(
+        self,
+        test_code: CodeBlock,
+        asynchronous: bool,
+        output: &mut TokenStream,
+    ) -> CodeBlock {
+        let span = self.location;
+
+        if let Some(feature) = self.feature {
+            output.extend([
+                punct('#', span),
+                bracketed(
+                    [
+                        ident("cfg", span),
+                        parenthesised(
+                            [
+                                ident("feature", span),
+                                punct('=', span),
+                                TokenTree::Literal(feature),
+                            ],
+                            span,
+                        ),
+                    ],
+                    span,
+                ),
+            ]);
+        }
+
+        let mut wrapped = CodeBlock::new();
+        wrapped.extend(code("let spoke_pending =", span));
+        if asynchronous {
+            wrapped.extend(teardown::catch_unwind_async(test_code, span));
+        } else {
+            wrapped.extend(catch_unwind(test_code, span));
+        }
+        wrapped.extend(code(
+            r#"; assert!(spoke_pending.is_err(), "this pending test now passes, remove $pending");"#,
+            span,
+        ));
+        wrapped
+    }
+}
+
+impl Teardown {
+    /// Returns the guarded code wrapped so that any panic is caught until the
+    /// teardown code has run, then resumed.
+    fn generate_around(self, guarded: CodeBlock, asynchronous: bool) -> CodeBlock {
+        let span = self.location;
+
+        let mut wrapped = CodeBlock::new();
+        wrapped.extend(code("let spoke_outcome =", span));
+        if asynchronous {
+            wrapped.extend(teardown::catch_unwind_async(guarded, span));
+        } else {
+            wrapped.extend(catch_unwind(guarded, span));
+        }
+        wrapped.push(punct(';', span));
+        wrapped.push(braced(self.code, span));
+        wrapped.extend(code(
+            "if let ::std::result::Result::Err(spoke_panic) = spoke_outcome { ::std::panic::resume_unwind(spoke_panic); }",
+            span,
+        ));
+        wrapped
+    }
 }
 
 impl CompilationError {
@@ -1,13 +1,22 @@
+mod approx;
+mod backend;
 mod code_block;
+mod collections;
+mod config;
+mod fixtures;
 mod generator;
+mod module_claims;
 mod name;
 mod parse;
 mod parse_rule;
 mod parser;
+mod power_assert;
 mod span_source;
 mod spoke;
 mod string_lit;
+mod teardown;
 mod token_helpers;
+mod type_check;
 
 /// # spoke::test!
 ///
@@ -48,11 +57,13 @@ mod token_helpers;
 /// Spoke isn't magic, it's just syntactic sugar over the existing test framework,
 /// as an example the above snippet produces:
 ///
-/// ```
+/// ```text
 ///#[cfg(test)]
 ///#[allow(unused_mut)]
 ///#[allow(unused_variables)]
-///mod spoketest {
+///mod spoketest_a_vector_15a3392f {
+///    #[allow(unused_imports)]
+///    use super::*;
 ///    #[test]
 ///    fn a_vector_starts_empty(){
 ///        let mut v = Vec::<u8>::new();
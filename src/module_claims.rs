@@ -0,0 +1,91 @@
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{LazyLock, Mutex},
+};
+
+use proc_macro2::{Ident, Span};
+
+/// The module names configured by every expansion within the current compilation,
+/// the compiler would reject two suites producing the same module in one file but the
+/// error it gives doesn't explain how to fix it. Derived names hold a hash of the
+/// suite so they are never claimed.
+static CLAIMS: LazyLock<Mutex<ModuleClaims>> = LazyLock::new(|| Mutex::new(ModuleClaims::new()));
+
+pub(crate) struct ModuleClaims {
+    claims: HashMap<(PathBuf, String), Claim>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) struct Claim {
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+    pub(crate) fingerprint: u32,
+}
+
+impl ModuleClaims {
+    pub(crate) fn new() -> Self {
+        Self {
+            claims: HashMap::new(),
+        }
+    }
+
+    /// Records the module as belonging to the suite at the claim's location, if a
+    /// different suite in the same file already owns it then that claim is returned.
+    /// A suite with the same contents is assumed to have moved (e.g. when an editor
+    /// re-expands it after the lines above were changed) rather than be a duplicate.
+    pub(crate) fn claim(
+        &mut self,
+        file: PathBuf,
+        module: &Ident,
+        claim: Claim,
+    ) -> Result<(), Claim> {
+        match self.claims.get_mut(&(file.clone(), module.to_string())) {
+            Some(existing)
+                if (existing.line, existing.column) != (claim.line, claim.column)
+                    && existing.fingerprint != claim.fingerprint =>
+            {
+                Err(*existing)
+            }
+            Some(existing) => {
+                *existing = claim;
+                Ok(())
+            }
+            None => {
+                self.claims.insert((file, module.to_string()), claim);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Claims the module for the macro invocation currently being expanded, this does
+/// nothing when the compiler can't tell us which file is being expanded.
+pub(crate) fn claim_module(module: &Ident, fingerprint: u32) -> Result<(), Claim> {
+    let call_site = Span::call_site();
+    let Some(file) = call_site.local_file() else {
+        return Ok(());
+    };
+
+    let start = call_site.start();
+    CLAIMS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .claim(
+            file,
+            module,
+            Claim {
+                line: start.line,
+                column: start.column,
+                fingerprint,
+            },
+        )
+}
+
+/// The error for a suite configured with a module that an earlier suite claimed.
+pub(crate) fn collision(module: &Ident, claim: Claim) -> String {
+    format!(
+        "the module `{}` is already generated by the spoke::test! at line {} of this file, use `$config(module = name)` to choose a different name",
+        module, claim.line
+    )
+}
@@ -1,5 +1,8 @@
-use crate::parser::{SpanSource, TestCase};
-use proc_macro2::Span;
+use crate::{
+    code_block::CodeBlock,
+    parser::{SpanSource, TestCase},
+};
+use proc_macro2::{Span, TokenStream, TokenTree};
 
 pub(crate) struct CompoundName<'a> {
     parts: NameParts<'a>,
@@ -14,6 +17,9 @@ impl<'a> CompoundName<'a> {
         self.parts.push(name);
         self
     }
+    pub(crate) fn is_empty(&self) -> bool {
+        self.parts.0.is_empty()
+    }
 }
 
 pub(crate) struct NameParts<'a>(Vec<&'a Name>);
@@ -46,6 +52,19 @@ impl Name {
         }
     }
 
+    /// Names a test after its own code, any dollars are dropped so that the
+    /// assertion types read as plain words.
+    pub(crate) fn from_code(location: &impl SpanSource, code: &CodeBlock) -> Self {
+        Self::new(
+            location,
+            code.iter()
+                .filter(|token| !matches!(token, TokenTree::Punct(p) if p.as_char() == '$'))
+                .cloned()
+                .collect::<TokenStream>()
+                .to_string(),
+        )
+    }
+
     pub(crate) fn missing(token: &impl SpanSource, id: usize) -> Name {
         Self::new(
             token,
@@ -70,7 +89,7 @@ impl<'a> CompoundName<'a> {
     pub(crate) fn function_name(self) -> (String, Span) {
         let mut name = self.parts.0.iter().fold(String::new(), |mut acc, b| {
             if !acc.is_empty() {
-                acc.push_str("_");
+                acc.push('_');
             }
             acc.push_str(&b.sanitised);
             acc
@@ -89,7 +108,7 @@ impl<'a> CompoundName<'a> {
     }
 }
 
-fn sanitise(text: &str) -> String {
+pub(crate) fn sanitise(text: &str) -> String {
     // this is hit or miss - assume most test names contain some special
     // characters and are likely to expand a bit
     let mut builder = WhitespaceSeparatedWords::new(text.len());
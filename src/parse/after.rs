@@ -0,0 +1,59 @@
+use crate::{parse, parser::*};
+
+/// `$after` followed by a braced block of code which is run at the end of each of
+/// the tests that follow it in the body, even when the test fails.
+pub(crate) struct TransientAfter {
+    parent: parse::AnchorParent,
+    anchor: Span,
+}
+
+impl TransientAfter {
+    pub(crate) fn new(parent: parse::AnchorParent, location: &impl SpanSource) -> Self {
+        Self {
+            parent,
+            anchor: location.span(),
+        }
+    }
+}
+
+impl Parser for TransientAfter {
+    fn accept_token(mut self, token: TokenTree, target: &mut SuiteGenerator) -> ParseRule {
+        match token {
+            TokenTree::Group(group) if group.delimiter() == Delimiter::Brace => {
+                let code = group.stream().into_iter().collect();
+                if let Some(shared) =
+                    parse::enclosing_code(&mut self.parent, "after", &self.anchor, target)
+                {
+                    shared.push_teardown(self.anchor, code);
+                }
+                self.parent.continuation()
+            }
+
+            other => {
+                target.push_new_error(
+                    &other,
+                    format!(
+                        "expected a braced block of code following `$after`, but found `{}`",
+                        other
+                    ),
+                );
+                parse::TransientAssertError::new(self.parent).forward_token(other, target)
+            }
+        }
+    }
+
+    fn end_of_group(self, target: &mut SuiteGenerator) -> ParseRule {
+        target.push_new_error(
+            &self.anchor,
+            "reached end of group input before finding the block of code for `$after`",
+        );
+        self.parent.continuation()
+    }
+
+    fn end_of_stream(self, target: &mut SuiteGenerator) {
+        target.push_new_error(
+            &self.anchor,
+            "reached end of input before finding the block of code for `$after`",
+        );
+    }
+}
@@ -1,19 +1,55 @@
 use std::fmt::Display;
 
-use proc_macro2::TokenTree;
+use proc_macro2::{Delimiter, Group, TokenTree};
 
-use crate::{name::Name, string_lit::TokenExtensions};
+use crate::{name::Name, parse, string_lit::TokenExtensions};
 
 pub(crate) enum Dollars {
     AssertEq,
     AssertNotEq,
+    AssertLt,
+    AssertLe,
+    AssertGt,
+    AssertGe,
+    AssertOk,
+    AssertErr,
+    AssertSome,
+    AssertNone,
+    Matches,
+    Approx,
+    Within,
+    Contains,
+    Len,
+    UnorderedEq,
+    Impl,
+    Type,
+    Panics,
+    OnFail,
 }
 
 impl Dollars {
     pub(crate) fn list() -> String {
         [
             Self::AssertEq.to_string(),
-            Self::AssertNotEq.to_string()
+            Self::AssertNotEq.to_string(),
+            Self::AssertLt.to_string(),
+            Self::AssertLe.to_string(),
+            Self::AssertGt.to_string(),
+            Self::AssertGe.to_string(),
+            Self::AssertOk.to_string(),
+            Self::AssertErr.to_string(),
+            Self::AssertSome.to_string(),
+            Self::AssertNone.to_string(),
+            Self::Matches.to_string(),
+            Self::Approx.to_string(),
+            Self::Within.to_string(),
+            Self::Contains.to_string(),
+            Self::Len.to_string(),
+            Self::UnorderedEq.to_string(),
+            Self::Impl.to_string(),
+            Self::Type.to_string(),
+            Self::Panics.to_string(),
+            Self::OnFail.to_string(),
         ].join(",")
     }
 }
@@ -23,6 +59,24 @@ impl Display for Dollars {
         match self {
             Dollars::AssertEq => write!(f, "eq"),
             Dollars::AssertNotEq =>  write!(f, "ne"),
+            Dollars::AssertLt => write!(f, "lt"),
+            Dollars::AssertLe => write!(f, "le"),
+            Dollars::AssertGt => write!(f, "gt"),
+            Dollars::AssertGe => write!(f, "ge"),
+            Dollars::AssertOk => write!(f, "ok"),
+            Dollars::AssertErr => write!(f, "err"),
+            Dollars::AssertSome => write!(f, "some"),
+            Dollars::AssertNone => write!(f, "none"),
+            Dollars::Matches => write!(f, "matches"),
+            Dollars::Approx => write!(f, "approx"),
+            Dollars::Within => write!(f, "within"),
+            Dollars::Contains => write!(f, "contains"),
+            Dollars::Len => write!(f, "len"),
+            Dollars::UnorderedEq => write!(f, "unordered_eq"),
+            Dollars::Impl => write!(f, "impl"),
+            Dollars::Type => write!(f, "type"),
+            Dollars::Panics => write!(f, "panics"),
+            Dollars::OnFail => write!(f, "onfail"),
         }
     }
 }
@@ -32,6 +86,16 @@ pub(crate) enum MatchResult {
     InvalidName(String),
     ValidDollars(Dollars),
     InvalidDollars(Dollars),
+    DataTable(Group),
+    Marker(parse::Marker),
+    AutoName,
+    Config,
+    Async,
+    Fork,
+    Const,
+    After,
+    Invariant,
+    Shared,
     OtherInvalid(String),
     SemiColon,
 }
@@ -46,15 +110,38 @@ pub(crate) fn dollars_match(tok: &TokenTree) -> MatchResult {
             Err(error) => MatchResult::InvalidName(format!("found `{}`\n{}", literal, error)),
         },
 
-        TokenTree::Ident(ident) => match dollars_ident_match(ident.to_string()) {
-            Ok(dollars) => MatchResult::ValidDollars(dollars),
-            Err(rejected) => match dollars_ident_match(rejected.to_ascii_lowercase()) {
-                Ok(dollars) => MatchResult::InvalidDollars(dollars),
-                Err(rejected) => MatchResult::OtherInvalid(rejected),
+        TokenTree::Ident(ident) if *ident == "config" => MatchResult::Config,
+
+        TokenTree::Ident(ident) if *ident == "async" => MatchResult::Async,
+
+        TokenTree::Ident(ident) if *ident == "fork" => MatchResult::Fork,
+
+        TokenTree::Ident(ident) if *ident == "const" => MatchResult::Const,
+
+        TokenTree::Ident(ident) if *ident == "after" => MatchResult::After,
+
+        TokenTree::Ident(ident) if *ident == "invariant" => MatchResult::Invariant,
+
+        TokenTree::Ident(ident) if *ident == "shared" => MatchResult::Shared,
+
+        TokenTree::Ident(ident) => match parse::marker_ident_match(ident.to_string()) {
+            Ok(marker) => MatchResult::Marker(marker),
+            Err(ident) => match dollars_ident_match(ident) {
+                Ok(dollars) => MatchResult::ValidDollars(dollars),
+                Err(rejected) => match dollars_ident_match(rejected.to_ascii_lowercase()) {
+                    Ok(dollars) => MatchResult::InvalidDollars(dollars),
+                    Err(rejected) => MatchResult::OtherInvalid(rejected),
+                },
             },
         },
         TokenTree::Punct(punct) if punct.as_char() == ';' => MatchResult::SemiColon,
 
+        TokenTree::Punct(punct) if punct.as_char() == '$' => MatchResult::AutoName,
+
+        TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis => {
+            MatchResult::DataTable(group.clone())
+        }
+
         other => MatchResult::OtherInvalid(other.to_string()),
     }
 }
@@ -63,6 +150,24 @@ pub(crate) fn dollars_ident_match(ident: String) -> Result<Dollars, String> {
     match ident.as_str() {
         "eq" => Result::Ok(Dollars::AssertEq),
         "ne" => Result::Ok(Dollars::AssertNotEq),
+        "lt" => Result::Ok(Dollars::AssertLt),
+        "le" => Result::Ok(Dollars::AssertLe),
+        "gt" => Result::Ok(Dollars::AssertGt),
+        "ge" => Result::Ok(Dollars::AssertGe),
+        "ok" => Result::Ok(Dollars::AssertOk),
+        "err" => Result::Ok(Dollars::AssertErr),
+        "some" => Result::Ok(Dollars::AssertSome),
+        "none" => Result::Ok(Dollars::AssertNone),
+        "matches" => Result::Ok(Dollars::Matches),
+        "approx" => Result::Ok(Dollars::Approx),
+        "within" => Result::Ok(Dollars::Within),
+        "contains" => Result::Ok(Dollars::Contains),
+        "len" => Result::Ok(Dollars::Len),
+        "unordered_eq" => Result::Ok(Dollars::UnorderedEq),
+        "impl" => Result::Ok(Dollars::Impl),
+        "type" => Result::Ok(Dollars::Type),
+        "panics" => Result::Ok(Dollars::Panics),
+        "onfail" => Result::Ok(Dollars::OnFail),
         other => Result::Err(other.to_string()),
     }
 }
@@ -1,7 +1,8 @@
 use crate::{
     code_block::CodeBlock,
     name::*,
-    parse::{self, Dollars, Negation},
+    backend::Comparison,
+    parse::{self, Dollars},
     parser::*,
     string_lit::TokenExtensions,
 };
@@ -127,37 +128,99 @@ impl TransientAssertAnchor {
         self,
         location: &impl SpanSource,
         assert_type: parse::Dollars,
-        _target: &mut SuiteGenerator,
+        target: &mut SuiteGenerator,
     ) -> ParseRule {
         match assert_type {
-            parse::Dollars::AssertEq => parse::AssertEq::new(
-                self.parent,
-                self.name,
-                Negation::Positive,
-                &self.anchor,
-                self.left_code,
-                location,
-            )
-            .consumed_token(),
-            parse::Dollars::AssertNotEq => parse::AssertEq::new(
+            parse::Dollars::AssertEq => self.route_comparison(Comparison::Eq, location),
+            parse::Dollars::AssertNotEq => self.route_comparison(Comparison::Ne, location),
+            parse::Dollars::AssertLt => self.route_comparison(Comparison::Lt, location),
+            parse::Dollars::AssertLe => self.route_comparison(Comparison::Le, location),
+            parse::Dollars::AssertGt => self.route_comparison(Comparison::Gt, location),
+            parse::Dollars::AssertGe => self.route_comparison(Comparison::Ge, location),
+            parse::Dollars::AssertOk => self.route_variant(parse::Variant::Ok, location),
+            parse::Dollars::AssertErr => self.route_variant(parse::Variant::Err, location),
+            parse::Dollars::AssertSome => self.route_variant(parse::Variant::Some, location),
+            parse::Dollars::AssertNone => self.route_variant(parse::Variant::None, location),
+            parse::Dollars::Matches => {
+                parse::AssertMatches::new(self.parent, self.name, self.left_code, location)
+                    .consumed_token()
+            }
+            parse::Dollars::Approx => {
+                parse::AssertApprox::new(self.parent, self.name, self.left_code, location)
+                    .consumed_token()
+            }
+            parse::Dollars::Within => {
+                target.push_new_error(
+                    location,
+                    "`$within` is only allowed following the expected value of an `$approx` assertion",
+                );
+                parse::TransientAssertError::new(self.parent).consumed_token()
+            }
+            parse::Dollars::Contains => self.route_collection(parse::Collection::Contains, location),
+            parse::Dollars::Len => self.route_collection(parse::Collection::Len, location),
+            parse::Dollars::UnorderedEq => {
+                self.route_collection(parse::Collection::UnorderedEq, location)
+            }
+            parse::Dollars::Impl => self.route_type_check(parse::TypeCheck::Impl, location, target),
+            parse::Dollars::Type => self.route_type_check(parse::TypeCheck::Type, location, target),
+            parse::Dollars::Panics => parse::AssertPanics::new(
                 self.parent,
                 self.name,
-                Negation::Negative,
-                &self.anchor,
                 self.left_code,
                 location,
             )
             .consumed_token(),
+            parse::Dollars::OnFail => {
+                parse::Assert::failing_with(self.parent, self.name, self.left_code, location)
+                    .consumed_token()
+            }
         }
     }
 }
 
+impl TransientAssertAnchor {
+    fn route_comparison(self, comparison: Comparison, location: &impl SpanSource) -> ParseRule {
+        parse::AssertEq::new(
+            self.parent,
+            self.name,
+            comparison,
+            &self.anchor,
+            self.left_code,
+            location,
+        )
+        .consumed_token()
+    }
+
+    fn route_variant(self, variant: parse::Variant, location: &impl SpanSource) -> ParseRule {
+        parse::AssertVariant::new(self.parent, self.name, variant, self.left_code, location)
+            .consumed_token()
+    }
+
+    fn route_collection(
+        self,
+        collection: parse::Collection,
+        location: &impl SpanSource,
+    ) -> ParseRule {
+        parse::AssertCollection::new(self.parent, self.name, collection, self.left_code, location)
+            .consumed_token()
+    }
+
+    fn route_type_check(
+        self,
+        check: parse::TypeCheck,
+        location: &impl SpanSource,
+        target: &mut SuiteGenerator,
+    ) -> ParseRule {
+        parse::AssertType::start(self.parent, self.name, check, self.left_code, location, target)
+    }
+}
+
 pub(crate) struct TransientAssertError {
     parent: parse::AnchorParent,
 }
 
 impl TransientAssertError {
-    fn new(parent: parse::AnchorParent) -> Self {
+    pub(crate) fn new(parent: parse::AnchorParent) -> Self {
         Self { parent }
     }
 }
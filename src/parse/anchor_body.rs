@@ -46,6 +46,20 @@ impl Parser for TransientBodyAnchor {
                     format!("found a badly formatted assertion '{}' which isn't allowed inside the braced body of a test and should be lowercase", dollars),
                     target
                 ),
+            parse::MatchResult::Marker(marker) => parse::TransientMarker::new(self.parent, marker, &token).consumed_token(),
+            parse::MatchResult::Config => self.expected_name(
+                    token,
+                    "found `config` which is only allowed in the suite preamble",
+                    target,
+                ),
+            parse::MatchResult::Async => parse::TransientAsync::new(self.parent, &token).consumed_token(),
+            parse::MatchResult::Fork => parse::TransientFork::new(self.parent, &token).consumed_token(),
+            parse::MatchResult::Const => parse::ConstAssert::start(self.parent, &token, target),
+            parse::MatchResult::After => parse::TransientAfter::new(self.parent, &token).consumed_token(),
+            parse::MatchResult::Invariant => parse::TransientInvariant::new(self.parent, &token).consumed_token(),
+            parse::MatchResult::Shared => parse::TransientSharedFixture::new(self.parent, &token).consumed_token(),
+            parse::MatchResult::AutoName => parse::TransientAutoNamed::new(self.parent, &token).consumed_token(),
+            parse::MatchResult::DataTable(header) => parse::DataTable::new(self.parent, header, target).consumed_token(),
             parse::MatchResult::OtherInvalid(found) => self.expected_name(token, format!("but found {}", found), target),
             parse::MatchResult::SemiColon => self.expected_name(
                     token,
@@ -84,6 +98,10 @@ impl TransientBodyNamed {
 
 impl Parser for TransientBodyNamed {
     fn accept_token(self, token: TokenTree, target: &mut SuiteGenerator) -> ParseRule {
+        if self.parent.collect_name_parts(CompoundName::new()).is_empty() {
+            target.name_suite(&self.name);
+        }
+
         match token {
             TokenTree::Group(group) if group.delimiter() == Delimiter::Brace => {
                 parse::Body::generate_body(self.parent, self.name, group, target)
@@ -118,7 +136,7 @@ pub(crate) struct TransientBodyNamingError {
 }
 
 impl TransientBodyNamingError {
-    fn new(parent: parse::AnchorParent, name: Name) -> Self {
+    pub(crate) fn new(parent: parse::AnchorParent, name: Name) -> Self {
         Self { parent, name }
     }
 }
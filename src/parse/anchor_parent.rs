@@ -3,6 +3,10 @@ use crate::{name::*, parse, parser::*};
 pub(crate) enum AnchorParent {
     Suite(parse::Suite),
     Body(Box<parse::Body>),
+    Row(Box<parse::DataRow>),
+    Marked(Box<parse::Marked>),
+    Async(Box<parse::AsyncBlock>),
+    Fork(Box<parse::ForkBlock>),
 }
 impl AnchorParent {
     pub(crate) fn from_body(body:parse::Body) -> Self {
@@ -11,10 +15,26 @@ impl AnchorParent {
     pub(crate) fn from_suite(suite:parse::Suite) -> Self {
         Self::Suite(suite)
     }
+    pub(crate) fn from_row(row: parse::DataRow) -> Self {
+        Self::Row(Box::new(row))
+    }
+    pub(crate) fn from_marked(marked: parse::Marked) -> Self {
+        Self::Marked(Box::new(marked))
+    }
+    pub(crate) fn from_async(block: parse::AsyncBlock) -> Self {
+        Self::Async(Box::new(block))
+    }
+    pub(crate) fn from_fork(block: parse::ForkBlock) -> Self {
+        Self::Fork(Box::new(block))
+    }
     pub(crate) fn continuation(self) -> ParseRule {
         match self {
             AnchorParent::Suite(suite) => ParseRule::Suite(suite),
             AnchorParent::Body(body) => ParseRule::Body(*body),
+            AnchorParent::Row(row) => ParseRule::DataRow(*row),
+            AnchorParent::Marked(marked) => marked.continuation(),
+            AnchorParent::Async(block) => block.continuation(),
+            AnchorParent::Fork(block) => block.continuation(),
         }
     }
 }
@@ -24,6 +44,10 @@ impl Nameable for AnchorParent {
         match &self {
             AnchorParent::Suite(_) => compound,
             AnchorParent::Body(body) => body.collect_name_parts(compound),
+            AnchorParent::Row(row) => row.collect_name_parts(compound),
+            AnchorParent::Marked(marked) => marked.collect_name_parts(compound),
+            AnchorParent::Async(block) => block.collect_name_parts(compound),
+            AnchorParent::Fork(block) => block.collect_name_parts(compound),
         }
     }
 }
@@ -33,6 +57,10 @@ impl Populator for AnchorParent {
         match &self {
             AnchorParent::Suite(_) => test,
             AnchorParent::Body(body) => body.populate_test(test),
+            AnchorParent::Row(row) => row.populate_test(test),
+            AnchorParent::Marked(marked) => marked.populate_test(test),
+            AnchorParent::Async(block) => block.populate_test(test),
+            AnchorParent::Fork(block) => block.populate_test(test),
         }
     }
 }
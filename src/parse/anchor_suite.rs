@@ -46,7 +46,25 @@ impl Parser for TransientSuiteAnchor {
                     format!("found a badly formatted assertion '{}' which isn't allowed as a top level test and should be lowercase", dollars),
                     target,
                 ),
-            parse::MatchResult::OtherInvalid(found) => self.expected_name(token, format!("found `{}`", found), target), 
+            parse::MatchResult::Marker(marker) => parse::TransientMarker::new(parse::AnchorParent::from_suite(self.parent), marker, &token).consumed_token(),
+            parse::MatchResult::Config => parse::TransientConfig::new(self.parent, &token).consumed_token(),
+            parse::MatchResult::Async => parse::TransientAsync::new(parse::AnchorParent::from_suite(self.parent), &token).consumed_token(),
+            parse::MatchResult::Fork => parse::TransientFork::new(parse::AnchorParent::from_suite(self.parent), &token).consumed_token(),
+            parse::MatchResult::Const => parse::ConstAssert::start(parse::AnchorParent::from_suite(self.parent), &token, target),
+            parse::MatchResult::After => self.expected_name(
+                    token,
+                    "found `after` which is only allowed inside the braced body of a test",
+                    target,
+                ),
+            parse::MatchResult::Invariant => self.expected_name(
+                    token,
+                    "found `invariant` which is only allowed inside the braced body of a test",
+                    target,
+                ),
+            parse::MatchResult::Shared => parse::TransientSharedFixture::new(parse::AnchorParent::from_suite(self.parent), &token).consumed_token(),
+            parse::MatchResult::AutoName => parse::TransientAutoNamed::new(parse::AnchorParent::from_suite(self.parent), &token).consumed_token(),
+            parse::MatchResult::DataTable(header) => parse::DataTable::new(parse::AnchorParent::from_suite(self.parent), header, target).consumed_token(),
+            parse::MatchResult::OtherInvalid(found) => self.expected_name(token, format!("found `{}`", found), target),
             parse::MatchResult::SemiColon => self.expected_name(
                     token,
                     "found `;` before any body was provided",
@@ -84,6 +102,8 @@ impl TransientSuiteNamed {
 }
 impl Parser for TransientSuiteNamed {
     fn accept_token(self, token: TokenTree, target: &mut SuiteGenerator) -> ParseRule {
+        target.name_suite(&self.name);
+
         match token {
             TokenTree::Group(group) if group.delimiter() == Delimiter::Brace => {
                 parse::Body::generate_body_in_suite(self.parent, self.name, group, target)
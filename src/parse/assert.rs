@@ -1,9 +1,21 @@
-use crate::{code_block::CodeBlock, name::*, parse, parser::*, token_helpers::*};
+use std::mem::take;
+
+use proc_macro2::TokenStream;
+
+use crate::{
+    code_block::CodeBlock,
+    name::*,
+    parse,
+    parser::*,
+    power_assert::Expression,
+    token_helpers::{braced, code, lit_string, punct},
+};
 
 pub(crate) struct Assert {
     parent: parse::AnchorParent,
     name: Name,
     left_code: CodeBlock,
+    failure: parse::FailureMessage,
 }
 impl Assert {
     pub(crate) fn from_suite(parent: parse::Suite, name: Name) -> Self {
@@ -15,10 +27,29 @@ impl Assert {
             parent,
             name,
             left_code: CodeBlock::new(),
+            failure: parse::FailureMessage::new(),
+        }
+    }
+
+    pub(crate) fn failing_with(
+        parent: parse::AnchorParent,
+        name: Name,
+        left_code: CodeBlock,
+        location: &impl SpanSource,
+    ) -> Self {
+        Self {
+            parent,
+            name,
+            left_code,
+            failure: parse::FailureMessage::started(location),
         }
     }
 
     fn generate_assert_test_into(&mut self, target: &mut SuiteGenerator) {
+        let Ok(failure) = self.failure.arguments(target) else {
+            return;
+        };
+
         let mut test = self.parent.populate_test(TestCase::new(
             self.parent
                 .collect_name_parts(CompoundName::new())
@@ -27,13 +58,51 @@ impl Assert {
         ));
 
         let location = self.name.span();
+        let left_code = take(&mut self.left_code);
+        let backend = target.backend();
 
-        test.push_code([
-            ident("assert", location),
-            punct('!', location),
-            parenthesised(std::mem::take(&mut self.left_code).into_iter(), location),
-            punct(';', location),
-        ]);
+        let check = match Expression::decompose(&left_code) {
+            Some(expression) if !backend.reports_expressions() => {
+                let report = parse::failure_report(
+                    (
+                        "assertion failed: {}",
+                        vec![
+                            punct(',', location),
+                            lit_string(
+                                &left_code
+                                    .iter()
+                                    .cloned()
+                                    .collect::<TokenStream>()
+                                    .to_string(),
+                                location,
+                            ),
+                        ],
+                    ),
+                    ("{}", code(", spoke_values", location).into_iter().collect()),
+                    failure,
+                    location,
+                );
+
+                let mut check: CodeBlock = code(
+                    "#[allow(unused_imports)]
+                     use spoke_power::{ViaDebug as _, WithoutDebug as _};
+                     let mut spoke_values = spoke_power::Values::new();
+                     let spoke_passed = ",
+                    location,
+                )
+                .into_iter()
+                .collect();
+                check.extend(expression.generate(location));
+                check.push(punct(';', location));
+                check.extend(backend.assert(code("spoke_passed", location), report, location));
+
+                target.use_power_assert();
+                vec![braced(check, location)]
+            }
+            _ => backend.assert(left_code, failure, location),
+        };
+
+        test.push_code(check);
 
         target.push_test(test);
     }
@@ -42,9 +111,9 @@ impl Assert {
 impl Parser for Assert {
     fn accept_token(mut self, token: TokenTree, target: &mut SuiteGenerator) -> ParseRule {
         match token {
-            TokenTree::Punct(punct) if punct.as_char() == '$' => {
+            TokenTree::Punct(punct) if punct.as_char() == '$' && !self.failure.is_started() => {
                 parse::TransientAssertAnchor::new(self.parent, self.name, self.left_code, &punct)
-                     .consumed_token()
+                    .consumed_token()
             }
 
             TokenTree::Punct(punct) if punct.as_char() == ';' => {
@@ -60,7 +129,9 @@ impl Parser for Assert {
             }
 
             other => {
-                self.left_code.push(other);
+                if let Some(code) = self.failure.accept_token(other, target) {
+                    self.left_code.push(code);
+                }
                 self.consumed_token()
             }
         }
@@ -0,0 +1,227 @@
+use std::mem::take;
+
+use proc_macro2::TokenStream;
+
+use crate::{
+    approx::Tolerance,
+    code_block::*,
+    name::*,
+    parse,
+    parser::*,
+    token_helpers::{braced, code, lit_string, parenthesised, punct},
+};
+
+/// `$approx` is followed by the expected value and then a `$within` tolerance, so
+/// unlike the other assertions its own dollars have to be picked out before the
+/// tokens are handed on to the failure message.
+pub(crate) struct AssertApprox {
+    parent: parse::AnchorParent,
+    name: Name,
+    anchor: Span,
+    left_code: CodeBlock,
+    expected: CodeBlock,
+    dollars: Option<Span>,
+    within: Option<Span>,
+    tolerance: CodeBlock,
+    failure: parse::FailureMessage,
+}
+
+impl AssertApprox {
+    pub(crate) fn new(
+        parent: parse::AnchorParent,
+        name: Name,
+        left_code: CodeBlock,
+        location: &impl SpanSource,
+    ) -> Self {
+        Self {
+            parent,
+            name,
+            anchor: location.span(),
+            left_code,
+            expected: CodeBlock::new(),
+            dollars: None,
+            within: None,
+            tolerance: CodeBlock::new(),
+            failure: parse::FailureMessage::new(),
+        }
+    }
+
+    fn push_code(&mut self, token: TokenTree) {
+        match self.within {
+            Some(_) => self.tolerance.push(token),
+            None => self.expected.push(token),
+        }
+    }
+
+    /// Passes a `$` that doesn't start `$within` on to the failure message, which
+    /// will report it if it isn't followed by `onfail`.
+    fn release_dollars(&mut self, target: &mut SuiteGenerator) {
+        if let Some(dollars) = self.dollars.take()
+            && let Some(code) = self.failure.accept_token(punct('$', dollars), target)
+        {
+            self.push_code(code);
+        }
+    }
+
+    fn generate_test(&mut self, target: &mut SuiteGenerator) {
+        let mut is_ok = true;
+
+        let location = self.anchor;
+        let left_code = take(&mut self.left_code);
+        let expected = take(&mut self.expected);
+        let tolerance = take(&mut self.tolerance);
+
+        if left_code.is_empty() {
+            target.push_new_error(
+                &location,
+                "no code found for the expression expected to approximately equal the value",
+            );
+            is_ok = false;
+        }
+
+        if expected.is_empty() {
+            target.push_new_error(
+                &location,
+                "no code found for the expected value of the approx assertion",
+            );
+            is_ok = false;
+        }
+
+        match self.within {
+            None => {
+                target.push_new_error(
+                    &location,
+                    "expected `$within` and a tolerance following the expected value of the approx assertion",
+                );
+                is_ok = false;
+            }
+            Some(within) if tolerance.is_empty() => {
+                target.push_new_error(&within, "expected a tolerance following `$within`");
+                is_ok = false;
+            }
+            Some(_) => (),
+        }
+
+        self.release_dollars(target);
+        let failure = self.failure.arguments(target);
+
+        let (true, Ok(failure)) = (is_ok, failure) else {
+            return;
+        };
+
+        let mut test = self.parent.populate_test(TestCase::new(
+            self.parent
+                .collect_name_parts(CompoundName::new())
+                .followed_by(&self.name)
+                .function_name(),
+        ));
+
+        let as_text = |tokens: &CodeBlock| {
+            lit_string(
+                &tokens.iter().cloned().collect::<TokenStream>().to_string(),
+                location,
+            )
+        };
+
+        let report = parse::failure_report(
+            (
+                "expected `{}` to approximately equal `{}`",
+                vec![
+                    punct(',', location),
+                    as_text(&left_code),
+                    punct(',', location),
+                    as_text(&expected),
+                ],
+            ),
+            (
+                "{}",
+                code(", spoke_mismatch.unwrap_or_default()", location)
+                    .into_iter()
+                    .collect(),
+            ),
+            failure,
+            location,
+        );
+
+        let (kind, tolerance) = Tolerance::from_tokens(tolerance);
+
+        let mut check: CodeBlock = code("let spoke_mismatch = spoke_approx::check", location)
+            .into_iter()
+            .collect();
+        check.push(parenthesised(
+            [punct('&', location), parenthesised(left_code, location)]
+                .into_iter()
+                .chain([
+                    punct(',', location),
+                    punct('&', location),
+                    parenthesised(expected, location),
+                    punct(',', location),
+                ])
+                .chain(kind.generate(tolerance, location)),
+            location,
+        ));
+        check.push(punct(';', location));
+        check.extend(target.backend().assert(
+            code("spoke_mismatch.is_none()", location),
+            report,
+            location,
+        ));
+
+        test.push_code([braced(check, location)]);
+
+        target.use_approx();
+        target.push_test(test);
+    }
+}
+
+impl Parser for AssertApprox {
+    fn accept_token(mut self, token: TokenTree, target: &mut SuiteGenerator) -> ParseRule {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == ';' => {
+                self.generate_test(target);
+                self.parent.continuation()
+            }
+
+            TokenTree::Ident(ident)
+                if self.dollars.is_some()
+                    && self.within.is_none()
+                    && parse::dollars_ident_match(ident.to_string())
+                        .is_ok_and(|d| matches!(d, parse::Dollars::Within)) =>
+            {
+                self.dollars = None;
+                self.within = Some(ident.span());
+                self.consumed_token()
+            }
+
+            TokenTree::Punct(punct) if punct.as_char() == '$' && !self.failure.is_started() => {
+                self.release_dollars(target);
+                self.dollars = Some(punct.span());
+                self.consumed_token()
+            }
+
+            other => {
+                self.release_dollars(target);
+                if let Some(code) = self.failure.accept_token(other, target) {
+                    self.push_code(code);
+                }
+                self.consumed_token()
+            }
+        }
+    }
+
+    fn end_of_group(self, target: &mut SuiteGenerator) -> ParseRule {
+        target.push_new_error(
+            &Span::call_site(),
+            "reached end of group input before reaching the end of the approx assertion definition",
+        );
+
+        self.parent.continuation()
+    }
+
+    fn end_of_stream(self, target: &mut SuiteGenerator) {
+        target.push_new_error(
+            &Span::call_site(),
+            "reached end of input before reaching the end of the approx assertion definition",
+        );
+    }
+}
@@ -0,0 +1,265 @@
+use std::{fmt::Display, mem::take};
+
+use proc_macro2::TokenStream;
+
+use crate::{
+    code_block::*,
+    name::*,
+    parse,
+    parser::*,
+    token_helpers::{braced, code, invisible, lit_string, parenthesised, punct},
+};
+
+/// The checks made on a whole collection, which report its items rather than just
+/// the two sides of a comparison.
+#[derive(Clone, Copy)]
+pub(crate) enum Collection {
+    Contains,
+    Len,
+    UnorderedEq,
+}
+
+impl Display for Collection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Collection::Contains => write!(f, "contains"),
+            Collection::Len => write!(f, "len"),
+            Collection::UnorderedEq => write!(f, "unordered_eq"),
+        }
+    }
+}
+
+pub(crate) struct AssertCollection {
+    parent: parse::AnchorParent,
+    name: Name,
+    anchor: Span,
+    collection: Collection,
+    left_code: CodeBlock,
+    right_code: CodeBlock,
+    failure: parse::FailureMessage,
+}
+
+impl AssertCollection {
+    pub(crate) fn new(
+        parent: parse::AnchorParent,
+        name: Name,
+        collection: Collection,
+        left_code: CodeBlock,
+        location: &impl SpanSource,
+    ) -> Self {
+        Self {
+            parent,
+            name,
+            anchor: location.span(),
+            collection,
+            left_code,
+            right_code: CodeBlock::new(),
+            failure: parse::FailureMessage::new(),
+        }
+    }
+
+    fn generate_test(&mut self, target: &mut SuiteGenerator) {
+        let mut is_ok = true;
+
+        let location = self.anchor;
+        let left_code = take(&mut self.left_code);
+        let right_code = take(&mut self.right_code);
+
+        if left_code.is_empty() {
+            target.push_new_error(
+                &location,
+                format!(
+                    "no code found for the left side of the {} assertion",
+                    self.collection
+                ),
+            );
+            is_ok = false;
+        }
+
+        if right_code.is_empty() {
+            target.push_new_error(
+                &location,
+                format!(
+                    "no code found for the right hand side of the {} assertion",
+                    self.collection
+                ),
+            );
+            is_ok = false;
+        }
+
+        let failure = self.failure.arguments(target);
+
+        let (true, Ok(failure)) = (is_ok, failure) else {
+            return;
+        };
+
+        let mut test = self.parent.populate_test(TestCase::new(
+            self.parent
+                .collect_name_parts(CompoundName::new())
+                .followed_by(&self.name)
+                .function_name(),
+        ));
+
+        let as_text = |tokens: &CodeBlock| {
+            lit_string(
+                &tokens.iter().cloned().collect::<TokenStream>().to_string(),
+                location,
+            )
+        };
+
+        let sides = vec![
+            punct(',', location),
+            as_text(&left_code),
+            punct(',', location),
+            as_text(&right_code),
+        ];
+
+        let check = match self.collection {
+            Collection::Len => {
+                let report = parse::failure_report(
+                    (
+                        "expected `{}` to have a length of {}, but it was {}",
+                        sides
+                            .into_iter()
+                            .take(2)
+                            .chain(code(", spoke_expected, spoke_collection.len()", location))
+                            .collect(),
+                    ),
+                    (
+                        "collection: {:?}",
+                        code(", spoke_collection", location).into_iter().collect(),
+                    ),
+                    failure,
+                    location,
+                );
+
+                let mut check: CodeBlock = code("match", location).into_iter().collect();
+                check.push(parenthesised(
+                    [punct('&', location), parenthesised(left_code, location)]
+                        .into_iter()
+                        .chain([punct(',', location), invisible(right_code, location)]),
+                    location,
+                ));
+                check.push(braced(
+                    code("(spoke_collection, spoke_expected) =>", location)
+                        .into_iter()
+                        .chain([braced(
+                            target.backend().assert(
+                                code("spoke_collection.len() == spoke_expected", location),
+                                report,
+                                location,
+                            ),
+                            location,
+                        )]),
+                    location,
+                ));
+                check
+            }
+
+            Collection::Contains | Collection::UnorderedEq => {
+                let (summary, mut check) = match self.collection {
+                    Collection::Contains => {
+                        let mut check: CodeBlock = code(
+                            "#[allow(unused_imports)]
+                             use spoke_collections::{ContainsItem as _, ContainsText as _};
+                             let spoke_mismatch = ",
+                            location,
+                        )
+                        .into_iter()
+                        .collect();
+                        check.push(parenthesised(
+                            code("&&spoke_collections::Haystack::new", location)
+                                .into_iter()
+                                .chain([parenthesised(left_code, location)]),
+                            location,
+                        ));
+                        check.extend(code(".spoke_contains", location));
+                        check.push(parenthesised(right_code, location));
+                        ("expected `{}` to contain `{}`", check)
+                    }
+                    _ => {
+                        let mut check: CodeBlock = code(
+                            "let spoke_mismatch = spoke_collections::unordered_eq",
+                            location,
+                        )
+                        .into_iter()
+                        .collect();
+                        check.push(parenthesised(
+                            left_code
+                                .into_iter()
+                                .chain([punct(',', location)])
+                                .chain(right_code),
+                            location,
+                        ));
+                        ("expected `{}` to equal `{}` in any order", check)
+                    }
+                };
+                check.push(punct(';', location));
+
+                let report = parse::failure_report(
+                    (summary, sides),
+                    (
+                        "{}",
+                        code(", spoke_mismatch.unwrap_or_default()", location)
+                            .into_iter()
+                            .collect(),
+                    ),
+                    failure,
+                    location,
+                );
+                check.extend(target.backend().assert(
+                    code("spoke_mismatch.is_none()", location),
+                    report,
+                    location,
+                ));
+
+                target.use_collections();
+                vec![braced(check, location)]
+            }
+        };
+
+        test.push_code(check);
+
+        target.push_test(test);
+    }
+}
+
+impl Parser for AssertCollection {
+    fn accept_token(mut self, token: TokenTree, target: &mut SuiteGenerator) -> ParseRule {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == ';' => {
+                self.generate_test(target);
+                self.parent.continuation()
+            }
+
+            other => {
+                if let Some(code) = self.failure.accept_token(other, target) {
+                    self.right_code.push(code);
+                }
+                self.consumed_token()
+            }
+        }
+    }
+
+    fn end_of_group(self, target: &mut SuiteGenerator) -> ParseRule {
+        target.push_new_error(
+            &Span::call_site(),
+            format!(
+                "reached end of group input before reaching the end of the {} assertion definition",
+                self.collection
+            ),
+        );
+
+        self.parent.continuation()
+    }
+
+    fn end_of_stream(self, target: &mut SuiteGenerator) {
+        target.push_new_error(
+            &Span::call_site(),
+            format!(
+                "reached end of input before reaching the end of the {} assertion definition",
+                self.collection
+            ),
+        );
+    }
+}
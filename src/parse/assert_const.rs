@@ -0,0 +1,231 @@
+use std::mem::take;
+
+use crate::{
+    backend::Comparison,
+    code_block::*,
+    name::*,
+    parse,
+    parser::*,
+    string_lit::TokenExtensions,
+    token_helpers::{code, ident, invisible, lit_string, parenthesised, punct},
+};
+
+/// `$const "requirement" expression;`, or a comparison using one of `$eq`, `$ne`,
+/// `$lt`, `$le`, `$gt` and `$ge`, checked when the crate is compiled rather than
+/// by a test. The check is a module item so none of the surrounding setup code or
+/// markers apply to it.
+pub(crate) struct ConstAssert {
+    parent: parse::AnchorParent,
+    anchor: Span,
+    requirement: Option<(Name, String)>,
+    left_code: CodeBlock,
+    expects_comparison: bool,
+    comparison: Option<(Comparison, Span)>,
+    right_code: CodeBlock,
+}
+
+impl ConstAssert {
+    pub(crate) fn start(
+        parent: parse::AnchorParent,
+        location: &impl SpanSource,
+        target: &mut SuiteGenerator,
+    ) -> ParseRule {
+        if let parse::AnchorParent::Marked(_) = parent {
+            target.push_new_error(
+                location,
+                "a `$const` assertion is checked at compile time so it can't be marked",
+            );
+            return parse::TransientAssertError::new(parent).consumed_token();
+        }
+
+        Self {
+            parent,
+            anchor: location.span(),
+            requirement: None,
+            left_code: CodeBlock::new(),
+            expects_comparison: false,
+            comparison: None,
+            right_code: CodeBlock::new(),
+        }
+        .consumed_token()
+    }
+
+    fn accept_requirement(self, token: TokenTree, target: &mut SuiteGenerator) -> ParseRule {
+        if let TokenTree::Literal(literal) = &token
+            && let Ok(requirement) = literal.as_string_literal()
+        {
+            let name = Name::new(literal, &requirement);
+            if self
+                .parent
+                .collect_name_parts(CompoundName::new())
+                .is_empty()
+            {
+                target.name_suite(&name);
+            }
+
+            return Self {
+                requirement: Some((name, requirement)),
+                ..self
+            }
+            .consumed_token();
+        }
+
+        target.push_new_error(
+            &token,
+            format!(
+                "expected a requirement in quotes following `$const`, but found `{}`",
+                token
+            ),
+        );
+        parse::TransientAssertError::new(self.parent).forward_token(token, target)
+    }
+
+    fn accept_comparison(mut self, token: TokenTree, target: &mut SuiteGenerator) -> ParseRule {
+        let comparison = match &token {
+            TokenTree::Ident(ident) => match parse::dollars_ident_match(ident.to_string()) {
+                Ok(parse::Dollars::AssertEq) => Some(Comparison::Eq),
+                Ok(parse::Dollars::AssertNotEq) => Some(Comparison::Ne),
+                Ok(parse::Dollars::AssertLt) => Some(Comparison::Lt),
+                Ok(parse::Dollars::AssertLe) => Some(Comparison::Le),
+                Ok(parse::Dollars::AssertGt) => Some(Comparison::Gt),
+                Ok(parse::Dollars::AssertGe) => Some(Comparison::Ge),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        match comparison {
+            Some(comparison) => {
+                self.comparison = Some((comparison, token.span()));
+                self.consumed_token()
+            }
+            None => {
+                target.push_new_error(
+                    &token,
+                    format!(
+                        "expected a comparison [eq,ne,lt,le,gt,ge] following the dollars of a `$const` assertion, but found `{}`",
+                        token
+                    ),
+                );
+                parse::TransientAssertError::new(self.parent).forward_token(token, target)
+            }
+        }
+    }
+
+    fn generate_assertion(&mut self, target: &mut SuiteGenerator) {
+        let Some((name, requirement)) = self.requirement.take() else {
+            return;
+        };
+
+        let location = name.span();
+        let left_code = take(&mut self.left_code);
+        let right_code = take(&mut self.right_code);
+
+        let condition = match self.comparison {
+            None if left_code.is_empty() => {
+                target.push_new_error(
+                    &name,
+                    "expected an expression following the requirement of the `$const` assertion, but found `;`",
+                );
+                return;
+            }
+            None => left_code,
+            Some((comparison, location)) => {
+                let mut is_ok = true;
+                if left_code.is_empty() {
+                    target.push_new_error(
+                        &location,
+                        "no code found for the left side of the const assertion",
+                    );
+                    is_ok = false;
+                }
+                if right_code.is_empty() {
+                    target.push_new_error(
+                        &location,
+                        "no code found for the right hand side of the const assertion",
+                    );
+                    is_ok = false;
+                }
+                if !is_ok {
+                    return;
+                }
+
+                let mut condition = vec![invisible(left_code, location)];
+                condition.extend(code(comparison.operator(), location));
+                condition.push(invisible(right_code, location));
+                condition
+            }
+        };
+
+        let mut item: CodeBlock = code("const _: () = ", location).into_iter().collect();
+        item.extend([
+            ident("assert", location),
+            punct('!', location),
+            parenthesised(
+                condition
+                    .into_iter()
+                    .chain([punct(',', location), lit_string(&requirement, location)]),
+                location,
+            ),
+            punct(';', location),
+        ]);
+
+        target.push_const_assertion(item);
+    }
+}
+
+impl Parser for ConstAssert {
+    fn accept_token(mut self, token: TokenTree, target: &mut SuiteGenerator) -> ParseRule {
+        if self.requirement.is_none() {
+            return self.accept_requirement(token, target);
+        }
+
+        if self.expects_comparison {
+            self.expects_comparison = false;
+            return self.accept_comparison(token, target);
+        }
+
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == ';' => {
+                self.generate_assertion(target);
+                self.parent.continuation()
+            }
+
+            TokenTree::Punct(punct) if punct.as_char() == '$' && self.comparison.is_none() => {
+                self.expects_comparison = true;
+                self.consumed_token()
+            }
+
+            TokenTree::Punct(punct) if punct.as_char() == '$' => {
+                target.push_new_error(
+                    &punct,
+                    "expected `;` following the right hand side of the `$const` assertion, but found `$`",
+                );
+                parse::TransientAssertError::new(self.parent).consumed_token()
+            }
+
+            other => {
+                match self.comparison {
+                    Some(_) => self.right_code.push(other),
+                    None => self.left_code.push(other),
+                }
+                self.consumed_token()
+            }
+        }
+    }
+
+    fn end_of_group(self, target: &mut SuiteGenerator) -> ParseRule {
+        target.push_new_error(
+            &self.anchor,
+            "reached end of group input before reaching the end of the `$const` assertion. Missing ; ?",
+        );
+        self.parent.continuation()
+    }
+
+    fn end_of_stream(self, target: &mut SuiteGenerator) {
+        target.push_new_error(
+            &self.anchor,
+            "reached end of input before reaching the end of the `$const` assertion. Missing ; ?",
+        );
+    }
+}
@@ -1,32 +1,22 @@
 use std::mem::take;
 
-use crate::{
-    code_block::*,
-    name::*,
-    parse,
-    parser::*,
-    token_helpers::{ident, parenthesised, punct},
-};
-
-pub(crate) enum Negation {
-    Positive,
-    Negative,
-}
+use crate::{backend::Comparison, code_block::*, name::*, parse, parser::*};
 
 pub(crate) struct AssertEq {
     parent: parse::AnchorParent,
     name: Name,
-    negation: Negation,
+    comparison: Comparison,
     anchor: Span,
     left_code: CodeBlock,
     right_code: CodeBlock,
+    failure: parse::FailureMessage,
 }
 
 impl AssertEq {
     pub(crate) fn new(
         parent: parse::AnchorParent,
         name: Name,
-        negation: Negation,
+        comparison: Comparison,
         _anchor: &impl SpanSource,
         left_code: CodeBlock,
         location: &impl SpanSource,
@@ -34,35 +24,46 @@ impl AssertEq {
         Self {
             parent,
             name,
-            negation,
+            comparison,
             anchor: location.span(),
             left_code,
             right_code: CodeBlock::new(),
+            failure: parse::FailureMessage::new(),
         }
     }
 
     fn generate_test(&mut self, target: &mut SuiteGenerator) {
         let mut is_ok = true;
 
+        let anchor = self.anchor;
         let left_code = take(&mut self.left_code);
+        let right_code = take(&mut self.right_code);
 
         if left_code.is_empty() {
             target.push_new_error(
-                &self.anchor,
-                "no code found for the left side of the equality assertion",
+                &anchor,
+                format!(
+                    "no code found for the left side of the {} assertion",
+                    self.comparison.kind()
+                ),
             );
             is_ok = false;
         }
 
-        if self.right_code.is_empty() {
+        if right_code.is_empty() {
             target.push_new_error(
-                &self.anchor,
-                "no code found for the right hand side of the equality assertion",
+                &anchor,
+                format!(
+                    "no code found for the right hand side of the {} assertion",
+                    self.comparison.kind()
+                ),
             );
             is_ok = false;
         }
 
-        if is_ok {
+        let failure = self.failure.arguments(target);
+
+        if let (true, Ok(failure)) = (is_ok, failure) {
             let mut test = self.parent.populate_test(TestCase::new(
                 self.parent
                     .collect_name_parts(CompoundName::new())
@@ -70,24 +71,13 @@ impl AssertEq {
                     .function_name(),
             ));
 
-            test.push_code([
-                ident(
-                    match self.negation {
-                        Negation::Positive => "assert_eq",
-                        Negation::Negative => "assert_ne",
-                    },
-                    self.anchor,
-                ),
-                punct('!', self.anchor),
-                parenthesised(
-                    left_code
-                        .into_iter()
-                        .chain([punct(',', self.anchor)])
-                        .chain(take(&mut self.right_code).into_iter()),
-                    self.anchor,
-                ),
-                punct(';', self.anchor),
-            ]);
+            test.push_code(target.backend().compare(
+                left_code,
+                self.comparison,
+                right_code,
+                failure,
+                anchor,
+            ));
 
             target.push_test(test);
         }
@@ -103,7 +93,9 @@ impl Parser for AssertEq {
             }
 
             other => {
-                self.right_code.push(other);
+                if let Some(code) = self.failure.accept_token(other, target) {
+                    self.right_code.push(code);
+                }
                 self.consumed_token()
             }
         }
@@ -112,7 +104,10 @@ impl Parser for AssertEq {
     fn end_of_group(self, target: &mut SuiteGenerator) -> ParseRule {
         target.push_new_error(
             &Span::call_site(),
-            "reached end of group input before reaching the end of the equality assertion definition",
+            format!(
+                "reached end of group input before reaching the end of the {} assertion definition",
+                self.comparison.kind()
+            ),
         );
 
         self.parent.continuation()
@@ -121,7 +116,10 @@ impl Parser for AssertEq {
     fn end_of_stream(self, target: &mut SuiteGenerator) {
         target.push_new_error(
             &Span::call_site(),
-            "reached end of input before reaching the end of the equality assertion definition",
+            format!(
+                "reached end of input before reaching the end of the {} assertion definition",
+                self.comparison.kind()
+            ),
         );
     }
 }
@@ -0,0 +1,158 @@
+use std::mem::take;
+
+use proc_macro2::TokenStream;
+
+use crate::{
+    code_block::*,
+    name::*,
+    parse,
+    parser::*,
+    token_helpers::{braced, code, lit_string, match_on, parenthesised, punct},
+};
+
+/// `$matches` takes a pattern rather than an expression on its right, optionally
+/// with an `if` guard, so it can't share the rule used for the comparisons.
+pub(crate) struct AssertMatches {
+    parent: parse::AnchorParent,
+    name: Name,
+    anchor: Span,
+    left_code: CodeBlock,
+    pattern: CodeBlock,
+    failure: parse::FailureMessage,
+}
+
+impl AssertMatches {
+    pub(crate) fn new(
+        parent: parse::AnchorParent,
+        name: Name,
+        left_code: CodeBlock,
+        location: &impl SpanSource,
+    ) -> Self {
+        Self {
+            parent,
+            name,
+            anchor: location.span(),
+            left_code,
+            pattern: CodeBlock::new(),
+            failure: parse::FailureMessage::new(),
+        }
+    }
+
+    fn generate_test(&mut self, target: &mut SuiteGenerator) {
+        let mut is_ok = true;
+
+        let location = self.anchor;
+        let left_code = take(&mut self.left_code);
+        let pattern = take(&mut self.pattern);
+
+        if left_code.is_empty() {
+            target.push_new_error(
+                &location,
+                "no code found for the expression expected to match the pattern",
+            );
+            is_ok = false;
+        }
+
+        if pattern.is_empty() {
+            target.push_new_error(&location, "no pattern found following `$matches`");
+            is_ok = false;
+        }
+
+        let failure = self.failure.arguments(target);
+
+        let (true, Ok(failure)) = (is_ok, failure) else {
+            return;
+        };
+
+        let mut test = self.parent.populate_test(TestCase::new(
+            self.parent
+                .collect_name_parts(CompoundName::new())
+                .followed_by(&self.name)
+                .function_name(),
+        ));
+
+        let as_text = |tokens: &CodeBlock| {
+            lit_string(
+                &tokens.iter().cloned().collect::<TokenStream>().to_string(),
+                location,
+            )
+        };
+
+        let report = parse::failure_report(
+            (
+                "expected `{}` to match `{}`",
+                vec![
+                    punct(',', location),
+                    as_text(&left_code),
+                    punct(',', location),
+                    as_text(&pattern),
+                ],
+            ),
+            (
+                " value: {:?}",
+                code(", spoke_value", location).into_iter().collect(),
+            ),
+            failure,
+            location,
+        );
+
+        // matching the reference leaves the value intact for the report, and binds
+        // the names in the pattern by reference so a guard can use any of them
+        let mut condition = code("matches!", location)
+            .into_iter()
+            .collect::<CodeBlock>();
+        condition.push(parenthesised(
+            code("spoke_value,", location).into_iter().chain(pattern),
+            location,
+        ));
+
+        let mut output: CodeBlock = match_on(left_code, location).into_iter().collect();
+        output.push(braced(
+            code("ref spoke_value =>", location)
+                .into_iter()
+                .chain([braced(
+                    target.backend().assert(condition, report, location),
+                    location,
+                )]),
+            location,
+        ));
+
+        test.push_code(output);
+
+        target.push_test(test);
+    }
+}
+
+impl Parser for AssertMatches {
+    fn accept_token(mut self, token: TokenTree, target: &mut SuiteGenerator) -> ParseRule {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == ';' => {
+                self.generate_test(target);
+                self.parent.continuation()
+            }
+
+            other => {
+                if let Some(code) = self.failure.accept_token(other, target) {
+                    self.pattern.push(code);
+                }
+                self.consumed_token()
+            }
+        }
+    }
+
+    fn end_of_group(self, target: &mut SuiteGenerator) -> ParseRule {
+        target.push_new_error(
+            &Span::call_site(),
+            "reached end of group input before reaching the end of the matches assertion definition",
+        );
+
+        self.parent.continuation()
+    }
+
+    fn end_of_stream(self, target: &mut SuiteGenerator) {
+        target.push_new_error(
+            &Span::call_site(),
+            "reached end of input before reaching the end of the matches assertion definition",
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use std::mem::take;
+
+use proc_macro2::TokenStream;
+
+use crate::{
+    code_block::*,
+    name::*,
+    parse,
+    parser::*,
+    string_lit::TokenExtensions,
+    teardown::catch_unwind_async,
+    token_helpers::{catch_unwind, code, ident, lit_string, parenthesised, punct},
+};
+
+pub(crate) struct AssertPanics {
+    parent: parse::AnchorParent,
+    name: Name,
+    anchor: Span,
+    left_code: CodeBlock,
+    expected: CodeBlock,
+    failure: parse::FailureMessage,
+}
+
+impl AssertPanics {
+    pub(crate) fn new(
+        parent: parse::AnchorParent,
+        name: Name,
+        left_code: CodeBlock,
+        location: &impl SpanSource,
+    ) -> Self {
+        Self {
+            parent,
+            name,
+            anchor: location.span(),
+            left_code,
+            expected: CodeBlock::new(),
+            failure: parse::FailureMessage::new(),
+        }
+    }
+
+    fn expected_message(&mut self, target: &mut SuiteGenerator) -> Result<Option<Literal>, ()> {
+        let mut expected = take(&mut self.expected).into_iter();
+
+        match (expected.next(), expected.next()) {
+            (None, _) => Ok(None),
+            (Some(TokenTree::Literal(literal)), None) if literal.as_string_literal().is_ok() => {
+                Ok(Some(literal))
+            }
+            (Some(first), _) => {
+                target.push_new_error(
+                    &first,
+                    format!(
+                        "expected either `;` or a panic message in quotes following the panics assertion, but found `{}`",
+                        first
+                    ),
+                );
+                Err(())
+            }
+        }
+    }
+
+    fn generate_test(&mut self, target: &mut SuiteGenerator) {
+        let left_code = take(&mut self.left_code);
+
+        if left_code.is_empty() {
+            target.push_new_error(
+                &self.anchor,
+                "no code found for the expression expected to panic",
+            );
+            return;
+        }
+
+        let (Ok(expected), Ok(failure)) = (
+            self.expected_message(target),
+            self.failure.arguments(target),
+        ) else {
+            return;
+        };
+
+        let mut test = self.parent.populate_test(TestCase::new(
+            self.parent
+                .collect_name_parts(CompoundName::new())
+                .followed_by(&self.name)
+                .function_name(),
+        ));
+
+        let location = self.anchor;
+        let expression = lit_string(
+            &left_code
+                .iter()
+                .cloned()
+                .collect::<TokenStream>()
+                .to_string(),
+            location,
+        );
+
+        let guarded = code("let _ =", location)
+            .into_iter()
+            .chain(left_code)
+            .chain([punct(';', location)]);
+
+        test.push_code(code("let spoke_panic =", location));
+        if test.is_async() {
+            // the expression may await so it has to be caught as a future
+            target.use_async_panics();
+            test.push_code(catch_unwind_async(guarded, location));
+        } else {
+            test.push_code(catch_unwind(guarded, location));
+        }
+        test.push_code([punct(';', location)]);
+
+        test.push_code(target.backend().assert(
+            code("spoke_panic.is_err()", location),
+            if failure.is_empty() {
+                vec![
+                    punct(',', location),
+                    lit_string("expected `{}` to panic", location),
+                    punct(',', location),
+                    expression.clone(),
+                ]
+            } else {
+                failure.clone()
+            },
+            location,
+        ));
+
+        if let Some(message) = expected {
+            test.push_code(code(
+                "let spoke_payload = spoke_panic.unwrap_err();
+                let spoke_panic_message = spoke_payload
+                    .downcast_ref::<&str>()
+                    .map(|m| m.to_string())
+                    .or_else(|| spoke_payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_default();",
+                location,
+            ));
+            test.push_code(target.backend().assert(
+                code("spoke_panic_message.contains", location)
+                    .into_iter()
+                    .chain([parenthesised(
+                        [TokenTree::Literal(message.clone())],
+                        location,
+                    )]),
+                if failure.is_empty() {
+                    vec![
+                        punct(',', location),
+                        lit_string(
+                            "expected `{}` to panic with a message containing {:?}, but the message was {:?}",
+                            location,
+                        ),
+                        punct(',', location),
+                        expression,
+                        punct(',', location),
+                        TokenTree::Literal(message),
+                        punct(',', location),
+                        ident("spoke_panic_message", location),
+                    ]
+                } else {
+                    failure
+                },
+                location,
+            ));
+        }
+
+        target.push_test(test);
+    }
+}
+
+impl Parser for AssertPanics {
+    fn accept_token(mut self, token: TokenTree, target: &mut SuiteGenerator) -> ParseRule {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == ';' => {
+                self.generate_test(target);
+                self.parent.continuation()
+            }
+
+            other => {
+                if let Some(code) = self.failure.accept_token(other, target) {
+                    self.expected.push(code);
+                }
+                self.consumed_token()
+            }
+        }
+    }
+
+    fn end_of_group(self, target: &mut SuiteGenerator) -> ParseRule {
+        target.push_new_error(
+            &Span::call_site(),
+            "reached end of group input before reaching the end of the panics assertion definition",
+        );
+
+        self.parent.continuation()
+    }
+
+    fn end_of_stream(self, target: &mut SuiteGenerator) {
+        target.push_new_error(
+            &Span::call_site(),
+            "reached end of input before reaching the end of the panics assertion definition",
+        );
+    }
+}
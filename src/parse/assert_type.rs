@@ -0,0 +1,211 @@
+use std::{fmt::Display, mem::take};
+
+use crate::{
+    code_block::*,
+    name::*,
+    parse,
+    parser::*,
+    token_helpers::{braced, code, ident, parenthesised, punct},
+};
+
+/// The checks that are made entirely by the compiler, the generated code only has
+/// to build for the assertion to hold and does nothing when it is run.
+#[derive(Clone, Copy)]
+pub(crate) enum TypeCheck {
+    /// `Type $impl Send + Sync`, the type meets the trait bounds.
+    Impl,
+    /// `expression $type u32`, the expression has exactly that type.
+    Type,
+}
+
+impl Display for TypeCheck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeCheck::Impl => write!(f, "impl"),
+            TypeCheck::Type => write!(f, "type"),
+        }
+    }
+}
+
+/// `Type $impl Bounds;` or `expression $type Type;`, nothing is checked at run time,
+/// a failure is a compile error at the assertion instead. `$impl` is an item of the
+/// suite module and `$type` a test that only has to build.
+pub(crate) struct AssertType {
+    parent: parse::AnchorParent,
+    name: Name,
+    anchor: Span,
+    check: TypeCheck,
+    left_code: CodeBlock,
+    right_code: CodeBlock,
+}
+
+impl AssertType {
+    pub(crate) fn start(
+        parent: parse::AnchorParent,
+        name: Name,
+        check: TypeCheck,
+        left_code: CodeBlock,
+        location: &impl SpanSource,
+        target: &mut SuiteGenerator,
+    ) -> ParseRule {
+        if let (TypeCheck::Impl, parse::AnchorParent::Marked(_)) = (check, &parent) {
+            target.push_new_error(
+                location,
+                "a `$impl` assertion is checked at compile time so it can't be marked",
+            );
+            return parse::TransientAssertError::new(parent).consumed_token();
+        }
+
+        Self {
+            parent,
+            name,
+            anchor: location.span(),
+            check,
+            left_code,
+            right_code: CodeBlock::new(),
+        }
+        .consumed_token()
+    }
+
+    fn generate_test(&mut self, target: &mut SuiteGenerator) {
+        let mut is_ok = true;
+
+        let location = self.anchor;
+        let left_code = take(&mut self.left_code);
+        let right_code = take(&mut self.right_code);
+
+        if left_code.is_empty() {
+            target.push_new_error(
+                &location,
+                match self.check {
+                    TypeCheck::Impl => "no type found for the `$impl` assertion",
+                    TypeCheck::Type => "no code found for the expression of the `$type` assertion",
+                },
+            );
+            is_ok = false;
+        }
+
+        if right_code.is_empty() {
+            target.push_new_error(
+                &location,
+                match self.check {
+                    TypeCheck::Impl => "expected trait bounds following `$impl`",
+                    TypeCheck::Type => "expected a type following `$type`",
+                },
+            );
+            is_ok = false;
+        }
+
+        if !is_ok {
+            return;
+        }
+
+        // the bounds don't depend on any setup, so like `$const` the check is an item of
+        // the module rather than a test that would run the code of its ancestors
+        if let TypeCheck::Impl = self.check {
+            target.push_const_assertion(implements(left_code, right_code, location));
+            return;
+        }
+
+        let mut test = self.parent.populate_test(TestCase::new(
+            self.parent
+                .collect_name_parts(CompoundName::new())
+                .followed_by(&self.name)
+                .function_name(),
+        ));
+
+        // never run, the expression only has to type check
+        let mut call: CodeBlock = code("spoke_types::check::<", location)
+            .into_iter()
+            .collect();
+        call.extend(right_code);
+        call.extend(code(", _>", location));
+        call.push(parenthesised(
+            [punct('&', location), parenthesised(left_code, location)],
+            location,
+        ));
+        call.push(punct(';', location));
+
+        target.use_types();
+        let check = [
+            ident("if", location),
+            ident("false", location),
+            braced(call, location),
+        ];
+
+        test.push_code([braced(check, location)]);
+
+        target.push_test(test);
+    }
+}
+
+/// `const _: () = { const fn spoke_implements<T: Bounds>() {} spoke_implements::<Type>(); };`,
+/// the bounds are those of a generic fn which is then given the type. `?Sized` lets
+/// unsized types through unless one of the bounds requires `Sized`.
+fn implements(type_code: CodeBlock, bounds: CodeBlock, location: Span) -> CodeBlock {
+    let mut check: CodeBlock = code(
+        "#[allow(clippy::needless_maybe_sized)] const fn spoke_implements<T: ?Sized + ",
+        location,
+    )
+    .into_iter()
+    .collect();
+    check.extend(bounds);
+    check.extend(code(">() {}", location));
+    check.extend(code("spoke_implements::<", location));
+    check.extend(type_code);
+    check.extend(code(">();", location));
+
+    let mut item: CodeBlock = code("const _: () =", location).into_iter().collect();
+    item.push(braced(check, location));
+    item.push(punct(';', location));
+    item
+}
+
+impl Parser for AssertType {
+    fn accept_token(mut self, token: TokenTree, target: &mut SuiteGenerator) -> ParseRule {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == ';' => {
+                self.generate_test(target);
+                self.parent.continuation()
+            }
+
+            TokenTree::Punct(punct) if punct.as_char() == '$' => {
+                target.push_new_error(
+                    &punct,
+                    format!(
+                        "a `${}` assertion is checked at compile time so it can't be followed by further dollars",
+                        self.check
+                    ),
+                );
+                parse::TransientAssertError::new(self.parent).consumed_token()
+            }
+
+            other => {
+                self.right_code.push(other);
+                self.consumed_token()
+            }
+        }
+    }
+
+    fn end_of_group(self, target: &mut SuiteGenerator) -> ParseRule {
+        target.push_new_error(
+            &Span::call_site(),
+            format!(
+                "reached end of group input before reaching the end of the {} assertion definition",
+                self.check
+            ),
+        );
+
+        self.parent.continuation()
+    }
+
+    fn end_of_stream(self, target: &mut SuiteGenerator) {
+        target.push_new_error(
+            &Span::call_site(),
+            format!(
+                "reached end of input before reaching the end of the {} assertion definition",
+                self.check
+            ),
+        );
+    }
+}
@@ -0,0 +1,242 @@
+use std::{fmt::Display, mem::take};
+
+use proc_macro2::TokenStream;
+
+use crate::{
+    backend::Comparison,
+    code_block::*,
+    name::*,
+    parse,
+    parser::*,
+    token_helpers::{braced, code, ident, lit_string, match_on, parenthesised, punct},
+};
+
+/// The shape of a `Result` or `Option` that a variant assertion expects.
+#[derive(Clone, Copy)]
+pub(crate) enum Variant {
+    Ok,
+    Err,
+    Some,
+    None,
+}
+
+impl Variant {
+    fn takes_value(&self) -> bool {
+        !matches!(self, Variant::None)
+    }
+
+    /// The variant that isn't expected, for reporting what was found instead.
+    fn opposite(&self) -> Variant {
+        match self {
+            Variant::Ok => Variant::Err,
+            Variant::Err => Variant::Ok,
+            Variant::Some => Variant::None,
+            Variant::None => Variant::Some,
+        }
+    }
+
+    fn binding(&self) -> &'static str {
+        match self {
+            Variant::Err => "spoke_error",
+            _ => "spoke_value",
+        }
+    }
+
+    fn pattern(&self, binding: Option<&str>, sp: Span) -> CodeBlock {
+        let mut pattern = vec![ident(&self.to_string(), sp)];
+        if self.takes_value() {
+            pattern.push(parenthesised([ident(binding.unwrap_or("_"), sp)], sp));
+        }
+        pattern
+    }
+}
+
+impl Display for Variant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Variant::Ok => write!(f, "Ok"),
+            Variant::Err => write!(f, "Err"),
+            Variant::Some => write!(f, "Some"),
+            Variant::None => write!(f, "None"),
+        }
+    }
+}
+
+pub(crate) struct AssertVariant {
+    parent: parse::AnchorParent,
+    name: Name,
+    anchor: Span,
+    variant: Variant,
+    left_code: CodeBlock,
+    expected: CodeBlock,
+    failure: parse::FailureMessage,
+}
+
+impl AssertVariant {
+    pub(crate) fn new(
+        parent: parse::AnchorParent,
+        name: Name,
+        variant: Variant,
+        left_code: CodeBlock,
+        location: &impl SpanSource,
+    ) -> Self {
+        Self {
+            parent,
+            name,
+            anchor: location.span(),
+            variant,
+            left_code,
+            expected: CodeBlock::new(),
+            failure: parse::FailureMessage::new(),
+        }
+    }
+
+    fn generate_test(&mut self, target: &mut SuiteGenerator) {
+        let left_code = take(&mut self.left_code);
+        let expected = take(&mut self.expected);
+
+        if left_code.is_empty() {
+            target.push_new_error(
+                &self.anchor,
+                format!(
+                    "no code found for the expression expected to be {}",
+                    self.variant
+                ),
+            );
+            return;
+        }
+
+        if !self.variant.takes_value() && !expected.is_empty() {
+            target.push_new_error(
+                &expected[0],
+                format!(
+                    "expected `;` following the {} assertion, but found `{}`",
+                    self.variant, expected[0]
+                ),
+            );
+            return;
+        }
+
+        let Ok(failure) = self.failure.arguments(target) else {
+            return;
+        };
+
+        let mut test = self.parent.populate_test(TestCase::new(
+            self.parent
+                .collect_name_parts(CompoundName::new())
+                .followed_by(&self.name)
+                .function_name(),
+        ));
+
+        let location = self.anchor;
+        let expression = lit_string(
+            &left_code
+                .iter()
+                .cloned()
+                .collect::<TokenStream>()
+                .to_string(),
+            location,
+        );
+
+        let expected_arm = match expected.is_empty() {
+            true => CodeBlock::new(),
+            false => target.backend().compare(
+                [ident(self.variant.binding(), location)],
+                Comparison::Eq,
+                expected,
+                failure.clone(),
+                location,
+            ),
+        };
+
+        let found = self.variant.opposite();
+        let mut report = match failure.is_empty() {
+            false => failure.into_iter().skip(1).collect(),
+            true if found.takes_value() => vec![
+                lit_string(
+                    &format!(
+                        "expected `{{}}` to be {}, but it was {}({{:?}})",
+                        self.variant, found
+                    ),
+                    location,
+                ),
+                punct(',', location),
+                expression,
+                punct(',', location),
+                ident(found.binding(), location),
+            ],
+            true => vec![
+                lit_string(
+                    &format!(
+                        "expected `{{}}` to be {}, but it was {}",
+                        self.variant, found
+                    ),
+                    location,
+                ),
+                punct(',', location),
+                expression,
+            ],
+        };
+        report = [ident("panic", location), punct('!', location)]
+            .into_iter()
+            .chain([parenthesised(report, location), punct(';', location)])
+            .collect();
+
+        let binding = match expected_arm.is_empty() {
+            true => None,
+            false => Some(self.variant.binding()),
+        };
+
+        let mut arms = self.variant.pattern(binding, location);
+        arms.extend(code("=>", location));
+        arms.push(braced(expected_arm, location));
+        arms.extend(found.pattern(Some(found.binding()), location));
+        arms.extend(code("=>", location));
+        arms.push(braced(report, location));
+
+        test.push_code(match_on(left_code, location));
+        test.push_code([braced(arms, location)]);
+
+        target.push_test(test);
+    }
+}
+
+impl Parser for AssertVariant {
+    fn accept_token(mut self, token: TokenTree, target: &mut SuiteGenerator) -> ParseRule {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == ';' => {
+                self.generate_test(target);
+                self.parent.continuation()
+            }
+
+            other => {
+                if let Some(code) = self.failure.accept_token(other, target) {
+                    self.expected.push(code);
+                }
+                self.consumed_token()
+            }
+        }
+    }
+
+    fn end_of_group(self, target: &mut SuiteGenerator) -> ParseRule {
+        target.push_new_error(
+            &Span::call_site(),
+            format!(
+                "reached end of group input before reaching the end of the {} assertion definition",
+                self.variant
+            ),
+        );
+
+        self.parent.continuation()
+    }
+
+    fn end_of_stream(self, target: &mut SuiteGenerator) {
+        target.push_new_error(
+            &Span::call_site(),
+            format!(
+                "reached end of input before reaching the end of the {} assertion definition",
+                self.variant
+            ),
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{name::*, parse, parser::*};
+
+/// `$async` optionally followed by runtime arguments in parentheses, which must
+/// then be followed by a braced block of tests.
+pub(crate) struct TransientAsync {
+    parent: parse::AnchorParent,
+    anchor: Span,
+    arguments: Option<Group>,
+}
+
+impl TransientAsync {
+    pub(crate) fn new(parent: parse::AnchorParent, location: &impl SpanSource) -> Self {
+        Self {
+            parent,
+            anchor: location.span(),
+            arguments: None,
+        }
+    }
+}
+
+impl Parser for TransientAsync {
+    fn accept_token(mut self, token: TokenTree, target: &mut SuiteGenerator) -> ParseRule {
+        match token {
+            TokenTree::Group(group)
+                if group.delimiter() == Delimiter::Parenthesis && self.arguments.is_none() =>
+            {
+                self.arguments = Some(group);
+                self.consumed_token()
+            }
+
+            TokenTree::Group(group) if group.delimiter() == Delimiter::Brace => {
+                AsyncBlock::generate_block(self, group, target)
+            }
+
+            other => {
+                target.push_new_error(
+                    &other,
+                    format!(
+                        "expected a braced block of tests following `$async`, but found `{}`",
+                        other
+                    ),
+                );
+                parse::TransientAssertError::new(self.parent).forward_token(other, target)
+            }
+        }
+    }
+
+    fn end_of_group(self, target: &mut SuiteGenerator) -> ParseRule {
+        target.push_new_error(
+            &self.anchor,
+            "reached end of group input before finding the block of tests for `$async`",
+        );
+        self.parent.continuation()
+    }
+
+    fn end_of_stream(self, target: &mut SuiteGenerator) {
+        target.push_new_error(
+            &self.anchor,
+            "reached end of input before finding the block of tests for `$async`",
+        );
+    }
+}
+
+/// A nameless block of tests which are all generated as async tests, any code
+/// in the block is shared by the tests inside it just like a named body.
+pub(crate) struct AsyncBlock {
+    parent: parse::AnchorParent,
+    location: Span,
+    arguments: Option<Group>,
+    code: parse::SharedCode,
+    has_children: bool,
+}
+
+impl AsyncBlock {
+    fn generate_block(
+        transient: TransientAsync,
+        group: Group,
+        target: &mut SuiteGenerator,
+    ) -> ParseRule {
+        let mut current_rule = ParseRule::AsyncBlock(Self {
+            parent: transient.parent,
+            location: transient.anchor,
+            arguments: transient.arguments,
+            code: parse::SharedCode::new(),
+            has_children: false,
+        });
+
+        for token in group.stream().into_iter() {
+            current_rule = current_rule.accept_token(token, target);
+        }
+
+        current_rule.end_of_group(target)
+    }
+
+    pub(crate) fn continuation(self) -> ParseRule {
+        ParseRule::AsyncBlock(self)
+    }
+
+    pub(crate) fn shared_code(&mut self) -> &mut parse::SharedCode {
+        &mut self.code
+    }
+}
+
+impl Parser for AsyncBlock {
+    fn accept_token(mut self, token: TokenTree, _: &mut SuiteGenerator) -> ParseRule {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == '$' => {
+                self.has_children = true;
+                parse::TransientBodyAnchor::new(parse::AnchorParent::from_async(self), &punct)
+                    .consumed_token()
+            }
+
+            other => {
+                self.code.push(other);
+                self.consumed_token()
+            }
+        }
+    }
+
+    fn end_of_group(self, target: &mut SuiteGenerator) -> ParseRule {
+        if !self.has_children {
+            target.push_new_error(
+                &self.location,
+                "expected at least one test inside the `$async` block",
+            );
+        }
+        self.parent.continuation()
+    }
+
+    fn end_of_stream(self, target: &mut SuiteGenerator) {
+        target.push_new_error(
+            &self.location,
+            "reached end of input before reaching the end of the `$async` block",
+        );
+    }
+}
+
+impl Nameable for AsyncBlock {
+    fn collect_name_parts<'a>(&'a self, compound: CompoundName<'a>) -> CompoundName<'a> {
+        self.parent.collect_name_parts(compound)
+    }
+}
+
+impl Populator for AsyncBlock {
+    fn populate_test(&self, test: TestCase) -> TestCase {
+        let mut test = self.code.populate_test(self.parent.populate_test(test));
+        test.make_async(&self.location, self.arguments.clone());
+        test
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{code_block::*, name::*, parse, parser::*};
+
+/// An assertion named with `$$`, the assertion is collected in full so that its code
+/// can become the name before being parsed as if it had been named by hand.
+pub(crate) struct TransientAutoNamed {
+    parent: parse::AnchorParent,
+    anchor: Span,
+    code: CodeBlock,
+}
+
+impl TransientAutoNamed {
+    pub(crate) fn new(parent: parse::AnchorParent, location: &impl SpanSource) -> Self {
+        Self {
+            parent,
+            anchor: location.span(),
+            code: CodeBlock::new(),
+        }
+    }
+
+    fn generate_assertion(self, end: TokenTree, target: &mut SuiteGenerator) -> ParseRule {
+        let name = match self.code.first() {
+            Some(first) => Name::from_code(first, &self.code),
+            None => Name::from_code(&self.anchor, &self.code),
+        };
+
+        let mut rule = parse::TransientBodyNamed::new(self.parent, name).consumed_token();
+        for token in self.code.into_iter().chain([end]) {
+            rule = rule.accept_token(token, target);
+        }
+        rule
+    }
+}
+
+impl Parser for TransientAutoNamed {
+    fn accept_token(mut self, token: TokenTree, target: &mut SuiteGenerator) -> ParseRule {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == ';' => {
+                self.generate_assertion(TokenTree::Punct(punct), target)
+            }
+
+            other => {
+                self.code.push(other);
+                self.consumed_token()
+            }
+        }
+    }
+
+    fn end_of_group(self, target: &mut SuiteGenerator) -> ParseRule {
+        target.push_new_error(
+            &self.anchor,
+            "reached end of group input before finding the end of the auto named assertion. Missing ; ?",
+        );
+        self.parent.continuation()
+    }
+
+    fn end_of_stream(self, target: &mut SuiteGenerator) {
+        target.push_new_error(
+            &self.anchor,
+            "reached end of input before finding the end of the auto named assertion. Missing ; ?",
+        );
+    }
+}
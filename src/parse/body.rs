@@ -1,9 +1,12 @@
-use crate::{code_block::*, name::*, parse, parser::*};
+use crate::{name::*, parse, parser::*};
 
 pub(crate) struct Body {
     parent: parse::AnchorParent,
     name: Name,
-    code: CodeBlock,
+    /// The code seen so far. Each child generates its tests as soon as it has been
+    /// parsed, so it only inherits the statements written before it and any that
+    /// follow, such as a mutation, apply to the later siblings alone.
+    code: parse::SharedCode,
     has_children: bool,
 }
 
@@ -26,7 +29,7 @@ impl Body {
         let mut current_rule = ParseRule::Body(Self {
             parent,
             name,
-            code: CodeBlock::new(),
+            code: parse::SharedCode::new(),
             has_children: false,
         });
 
@@ -37,6 +40,10 @@ impl Body {
         current_rule.end_of_group(target)
     }
 
+    pub(crate) fn shared_code(&mut self) -> &mut parse::SharedCode {
+        &mut self.code
+    }
+
     fn generate_test(&self, target: &mut SuiteGenerator) {
         if !self.has_children {
             target.push_test(self.populate_test(TestCase::new(
@@ -74,9 +81,7 @@ impl Parser for Body {
 
 impl Populator for Body {
     fn populate_test(&self, test: TestCase) -> TestCase {
-        let mut test = self.parent.populate_test(test);
-        test.push_code(self.code.clone());
-        test
+        self.code.populate_test(self.parent.populate_test(test))
     }
 }
 
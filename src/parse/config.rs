@@ -0,0 +1,211 @@
+use crate::{
+    code_block::*,
+    config::{AsyncRuntime, ConfigOption, config_option_match},
+    parse,
+    parser::*,
+};
+
+/// `$config(option = value, ...);` which is only allowed in the suite preamble,
+/// before any tests have been defined.
+pub(crate) struct TransientConfig {
+    parent: parse::Suite,
+    anchor: Span,
+    has_options: bool,
+}
+
+impl TransientConfig {
+    pub(crate) fn new(parent: parse::Suite, location: &impl SpanSource) -> Self {
+        Self {
+            parent,
+            anchor: location.span(),
+            has_options: false,
+        }
+    }
+
+    fn apply_options(&mut self, options: Group, target: &mut SuiteGenerator) {
+        self.has_options = true;
+
+        if target.has_tests() {
+            target.push_new_error(
+                &self.anchor,
+                "`$config` is only allowed in the suite preamble, before any tests are defined",
+            );
+            return;
+        }
+
+        for option in parse::split_on_commas(options.stream()) {
+            self.apply_option(&options, option, target);
+        }
+    }
+
+    fn apply_option(&self, options: &Group, option: CodeBlock, target: &mut SuiteGenerator) {
+        let mut option = option.into_iter();
+
+        let key = match option.next() {
+            Some(TokenTree::Ident(key)) => key,
+            Some(other) => {
+                target.push_new_error(
+                    &other,
+                    format!(
+                        "expected one of the config options [{}], but found `{}`",
+                        ConfigOption::list(),
+                        other
+                    ),
+                );
+                return;
+            }
+            None => {
+                target.push_new_error(
+                    options,
+                    format!(
+                        "expected one of the config options [{}], but found an empty option",
+                        ConfigOption::list()
+                    ),
+                );
+                return;
+            }
+        };
+
+        let config_option = match config_option_match(key.to_string()) {
+            Ok(config_option) => config_option,
+            Err(unknown) => {
+                target.push_new_error(
+                    &key,
+                    format!(
+                        "expected one of the config options [{}], but found `{}`",
+                        ConfigOption::list(),
+                        unknown
+                    ),
+                );
+                return;
+            }
+        };
+
+        match option.next() {
+            Some(TokenTree::Punct(punct)) if punct.as_char() == '=' => {}
+            _ => {
+                target.push_new_error(
+                    &key,
+                    format!("expected `=` and a value following the `{}` option", key),
+                );
+                return;
+            }
+        }
+
+        if target.config().is_set(&config_option) {
+            target.push_new_error(
+                &key,
+                format!("the `{}` option has already been configured", key),
+            );
+            return;
+        }
+
+        match (config_option, option.next(), option.next()) {
+            (ConfigOption::Module, Some(TokenTree::Ident(module)), None) => {
+                target.config().set_module(module);
+            }
+            (ConfigOption::Super, Some(TokenTree::Ident(switch)), None)
+                if switch == "on" || switch == "off" =>
+            {
+                target.config().set_import_super(switch == "on");
+            }
+            (ConfigOption::Async, Some(TokenTree::Ident(kind)), Some(TokenTree::Punct(equals)))
+                if (kind == "derive" || kind == "fn") && equals.as_char() == '=' =>
+            {
+                let runtime: CodeBlock = option.collect();
+                if runtime.is_empty() {
+                    target.push_new_error(
+                        &equals,
+                        format!(
+                            "expected the path of the async runtime following `{} =`, but found nothing",
+                            kind
+                        ),
+                    );
+                } else if kind == "derive" {
+                    target
+                        .config()
+                        .set_async_runtime(AsyncRuntime::Derive(runtime));
+                } else {
+                    target
+                        .config()
+                        .set_async_runtime(AsyncRuntime::Function(runtime));
+                }
+            }
+            (config_option, value, _) => {
+                let expected = match config_option {
+                    ConfigOption::Module => "a module name",
+                    ConfigOption::Super => "either `on` or `off`",
+                    ConfigOption::Async => "either `derive = path` or `fn = path`",
+                };
+                match value {
+                    Some(value) => target.push_new_error(
+                        &value,
+                        format!(
+                            "expected {} for the `{}` option, but found `{}`",
+                            expected, config_option, value
+                        ),
+                    ),
+                    None => target.push_new_error(
+                        &key,
+                        format!(
+                            "expected {} for the `{}` option, but found nothing",
+                            expected, config_option
+                        ),
+                    ),
+                }
+            }
+        }
+    }
+}
+
+impl Parser for TransientConfig {
+    fn accept_token(mut self, token: TokenTree, target: &mut SuiteGenerator) -> ParseRule {
+        match token {
+            TokenTree::Group(group)
+                if group.delimiter() == Delimiter::Parenthesis && !self.has_options =>
+            {
+                self.apply_options(group, target);
+                self.consumed_token()
+            }
+
+            TokenTree::Punct(punct) if punct.as_char() == ';' && self.has_options => {
+                ParseRule::Suite(self.parent)
+            }
+
+            other => {
+                target.push_new_error(
+                    &other,
+                    if self.has_options {
+                        format!("expected `;` following the config options, but found `{}`", other)
+                    } else {
+                        format!(
+                            "expected config options in parentheses following `$config`, but found `{}`",
+                            other
+                        )
+                    },
+                );
+                match other {
+                    TokenTree::Punct(punct) if punct.as_char() == ';' => {
+                        ParseRule::Suite(self.parent)
+                    }
+                    _ => self.consumed_token(),
+                }
+            }
+        }
+    }
+
+    fn end_of_group(self, target: &mut SuiteGenerator) -> ParseRule {
+        target.push_new_error(
+            &self.anchor,
+            "reached end of group input before reaching the end of the config definition",
+        );
+        ParseRule::Suite(self.parent)
+    }
+
+    fn end_of_stream(self, target: &mut SuiteGenerator) {
+        target.push_new_error(
+            &self.anchor,
+            "reached end of input before reaching the end of the config definition",
+        );
+    }
+}
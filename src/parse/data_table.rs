@@ -0,0 +1,441 @@
+use proc_macro2::TokenStream;
+
+use crate::{
+    code_block::*,
+    name::*,
+    parse,
+    parser::*,
+    string_lit::TokenExtensions,
+    token_helpers::{Spacing, ident, punct},
+};
+
+struct Column {
+    name: Ident,
+    ty: CodeBlock,
+}
+
+struct Row {
+    location: Span,
+    values: Vec<CodeBlock>,
+}
+
+pub(crate) struct DataTable {
+    parent: parse::AnchorParent,
+    anchor: Span,
+    columns: Vec<Column>,
+    rows: Vec<Row>,
+}
+
+impl DataTable {
+    pub(crate) fn new(
+        parent: parse::AnchorParent,
+        header: Group,
+        target: &mut SuiteGenerator,
+    ) -> Self {
+        let mut columns = Vec::new();
+
+        for segment in split_columns(header.stream()) {
+            let mut segment = segment.into_iter();
+            match (segment.next(), segment.next()) {
+                (Some(TokenTree::Ident(name)), None) => columns.push(Column {
+                    name,
+                    ty: CodeBlock::new(),
+                }),
+                (Some(TokenTree::Ident(name)), Some(TokenTree::Punct(colon)))
+                    if colon.as_char() == ':' =>
+                {
+                    let mut ty: CodeBlock = segment.collect();
+                    // a type followed by a comma ends with a joint `>` which would
+                    // otherwise join with the `=` of the binding
+                    if let Some(TokenTree::Punct(last)) = ty.last_mut() {
+                        let mut alone = Punct::new(last.as_char(), Spacing::Alone);
+                        alone.set_span(last.span());
+                        *last = alone;
+                    }
+                    columns.push(Column { name, ty })
+                }
+                (Some(other), _) => target.push_new_error(
+                    &other,
+                    format!(
+                        "expected a variable name for each column of the data table, but found `{}`",
+                        other
+                    ),
+                ),
+                (None, _) => target.push_new_error(
+                    &header,
+                    "expected a variable name for each column of the data table, but found an empty column",
+                ),
+            }
+        }
+
+        Self {
+            parent,
+            anchor: header.span(),
+            columns,
+            rows: Vec::new(),
+        }
+    }
+
+    fn push_row(&mut self, row: Group, target: &mut SuiteGenerator) {
+        let values = split_on_commas(row.stream());
+
+        if values.len() != self.columns.len() {
+            target.push_new_error(
+                &row,
+                format!(
+                    "expected {} values in the data row to match the data table header, but found {}",
+                    self.columns.len(),
+                    values.len()
+                ),
+            );
+        } else {
+            self.rows.push(Row {
+                location: row.span(),
+                values,
+            });
+        }
+    }
+
+    fn bindings(&self, row: &Row) -> CodeBlock {
+        let mut bindings = CodeBlock::new();
+
+        for (column, value) in self.columns.iter().zip(&row.values) {
+            bindings.push(ident("let", row.location));
+            bindings.push(TokenTree::Ident(column.name.clone()));
+            if !column.ty.is_empty() {
+                bindings.push(punct(':', row.location));
+                bindings.extend(column.ty.iter().cloned());
+            }
+            bindings.push(punct('=', row.location));
+            bindings.extend(value.iter().cloned());
+            bindings.push(punct(';', row.location));
+        }
+
+        bindings
+    }
+
+    fn interpolate(&self, template: &str, row: &Row) -> String {
+        self.columns
+            .iter()
+            .zip(&row.values)
+            .fold(template.to_string(), |name, (column, value)| {
+                name.replace(&format!("{{{}}}", column.name), &value_text(value))
+            })
+    }
+}
+
+impl Parser for DataTable {
+    fn accept_token(mut self, token: TokenTree, target: &mut SuiteGenerator) -> ParseRule {
+        match token {
+            TokenTree::Group(group) if group.delimiter() == Delimiter::Bracket => {
+                self.push_row(group, target);
+                self.consumed_token()
+            }
+
+            TokenTree::Literal(literal) => match literal.as_string_literal() {
+                Ok(template) => DataTableNamed::new(self, template, &literal).consumed_token(),
+                Err(error) => {
+                    target.push_new_error(
+                        &literal,
+                        format!(
+                            "expected a valid test name in quotes following the data table, but found `{}`\n{}",
+                            literal, error
+                        ),
+                    );
+                    parse::TransientAssertError::new(self.parent).consumed_token()
+                }
+            },
+
+            other => {
+                target.push_new_error(
+                    &other,
+                    format!(
+                        "expected a data row in brackets or a test name in quotes following the data table, but found `{}`",
+                        other
+                    ),
+                );
+                parse::TransientAssertError::new(self.parent).forward_token(other, target)
+            }
+        }
+    }
+
+    fn end_of_group(self, target: &mut SuiteGenerator) -> ParseRule {
+        target.push_new_error(
+            &self.anchor,
+            "reached end of group input before reaching the end of the data table definition",
+        );
+        self.parent.continuation()
+    }
+
+    fn end_of_stream(self, target: &mut SuiteGenerator) {
+        target.push_new_error(
+            &self.anchor,
+            "reached end of input before reaching the end of the data table definition",
+        );
+    }
+}
+
+pub(crate) struct DataTableNamed {
+    table: DataTable,
+    template: String,
+    location: Span,
+    definition: CodeBlock,
+}
+
+impl DataTableNamed {
+    fn new(table: DataTable, template: String, location: &impl SpanSource) -> Self {
+        Self {
+            table,
+            template,
+            location: location.span(),
+            definition: CodeBlock::new(),
+        }
+    }
+
+    /// Replays the buffered test definition once for each row of the table, each
+    /// time beneath a [`DataRow`] which prepends that row's bindings to the tests.
+    fn generate_rows(self, target: &mut SuiteGenerator) -> ParseRule {
+        let DataTableNamed {
+            table,
+            template,
+            location,
+            definition,
+        } = self;
+
+        if table.rows.is_empty() {
+            target.push_new_error(
+                &location,
+                "expected at least one data row in brackets between the data table and the test name",
+            );
+        }
+
+        let names = table
+            .rows
+            .iter()
+            .map(|row| table.interpolate(&template, row))
+            .collect::<Vec<_>>();
+
+        // rows whose names only differ by punctuation, or not at all, would generate
+        // the same function so they are told apart by their position in the table
+        let rows = table
+            .rows
+            .iter()
+            .zip(&names)
+            .enumerate()
+            .map(|(index, (row, name))| {
+                let clashes = names
+                    .iter()
+                    .filter(|other| sanitise(other) == sanitise(name))
+                    .count()
+                    > 1;
+                let name = if clashes {
+                    format!("{} row {}", name, index + 1)
+                } else {
+                    name.clone()
+                };
+                (Name::new(&location, name), table.bindings(row))
+            })
+            .collect::<Vec<_>>();
+
+        let mut parent = table.parent;
+
+        for (name, bindings) in rows {
+            let row_parent = parse::AnchorParent::from_row(DataRow { parent, bindings });
+
+            let mut rule = parse::TransientBodyNamed::new(row_parent, name).consumed_token();
+            for token in definition.iter().cloned() {
+                rule = rule.accept_token(token, target);
+            }
+
+            parent = match rule {
+                ParseRule::DataRow(row) => row.parent,
+                unexpected => return unexpected,
+            };
+        }
+
+        parent.continuation()
+    }
+}
+
+impl Parser for DataTableNamed {
+    fn accept_token(mut self, token: TokenTree, target: &mut SuiteGenerator) -> ParseRule {
+        match token {
+            TokenTree::Group(group)
+                if group.delimiter() == Delimiter::Brace && self.definition.is_empty() =>
+            {
+                self.definition.push(TokenTree::Group(group));
+                self.generate_rows(target)
+            }
+
+            TokenTree::Punct(punct) if punct.as_char() == ';' => {
+                self.definition.push(TokenTree::Punct(punct));
+                self.generate_rows(target)
+            }
+
+            other => {
+                self.definition.push(other);
+                self.consumed_token()
+            }
+        }
+    }
+
+    fn end_of_group(self, target: &mut SuiteGenerator) -> ParseRule {
+        target.push_new_error(
+            &self.location,
+            "reached end of group input before finding the test body for the data table",
+        );
+        self.table.parent.continuation()
+    }
+
+    fn end_of_stream(self, target: &mut SuiteGenerator) {
+        target.push_new_error(
+            &self.location,
+            "reached end of input before finding the test body for the data table",
+        );
+    }
+}
+
+/// The parent of a single replayed row of a [`DataTable`], once the row's test
+/// definition is complete it hands control straight back to the table's parent.
+pub(crate) struct DataRow {
+    parent: parse::AnchorParent,
+    bindings: CodeBlock,
+}
+
+impl Parser for DataRow {
+    fn accept_token(self, token: TokenTree, target: &mut SuiteGenerator) -> ParseRule {
+        self.parent.continuation().accept_token(token, target)
+    }
+
+    fn end_of_group(self, target: &mut SuiteGenerator) -> ParseRule {
+        self.parent.continuation().end_of_group(target)
+    }
+
+    fn end_of_stream(self, target: &mut SuiteGenerator) {
+        self.parent.continuation().end_of_stream(target)
+    }
+}
+
+impl Nameable for DataRow {
+    fn collect_name_parts<'a>(&'a self, compound: CompoundName<'a>) -> CompoundName<'a> {
+        self.parent.collect_name_parts(compound)
+    }
+}
+
+impl Populator for DataRow {
+    fn populate_test(&self, test: TestCase) -> TestCase {
+        let mut test = self.parent.populate_test(test);
+        test.push_code(self.bindings.clone());
+        test
+    }
+}
+
+/// Splits the values of a data row, or the options of a `$config`, on their commas. A
+/// comma belongs to the value when it is between the angle brackets of a turbofish or
+/// the parameters of a closure, any other `<` in a value is taken to be a comparison.
+pub(crate) fn split_on_commas(stream: TokenStream) -> Vec<CodeBlock> {
+    let mut segments = vec![CodeBlock::new()];
+    let mut depth = 0usize;
+    let mut closure_parameters = false;
+    let mut previous: Option<TokenTree> = None;
+
+    for token in stream {
+        let current = punct_char(Some(&token));
+        let before = punct_char(previous.as_ref());
+
+        match current {
+            Some(',') if depth == 0 && !closure_parameters => {
+                segments.push(CodeBlock::new());
+                previous = None;
+                continue;
+            }
+            Some('<')
+                if depth > 0 || closure_parameters || before == Some(':') || previous.is_none() =>
+            {
+                depth += 1
+            }
+            // the `>` of a `->` in a function type doesn't close a bracket
+            Some('>') if depth > 0 && before != Some('-') => depth -= 1,
+            Some('|') if closure_parameters => closure_parameters = false,
+            // a closure starts a value or follows an operator or `move`, otherwise it is a bitwise or
+            Some('|') if opens_closure(previous.as_ref()) => closure_parameters = true,
+            _ => {}
+        }
+
+        previous = Some(token.clone());
+        segments
+            .last_mut()
+            .expect("there is always a segment")
+            .push(token);
+    }
+
+    if segments.last().is_some_and(|segment| segment.is_empty()) {
+        segments.pop();
+    }
+
+    segments
+}
+
+fn punct_char(token: Option<&TokenTree>) -> Option<char> {
+    match token {
+        Some(TokenTree::Punct(punct)) => Some(punct.as_char()),
+        _ => None,
+    }
+}
+
+fn opens_closure(previous: Option<&TokenTree>) -> bool {
+    match previous {
+        None => true,
+        Some(TokenTree::Punct(punct)) => punct.as_char() != '|',
+        Some(TokenTree::Ident(ident)) => ident == "move",
+        _ => false,
+    }
+}
+
+/// Splits the header of a table into its columns, unlike the rows the header only
+/// holds names and types so any commas between angle brackets belong to a type.
+fn split_columns(stream: TokenStream) -> Vec<CodeBlock> {
+    let mut segments = vec![CodeBlock::new()];
+    let mut depth = 0usize;
+    let mut previous: Option<char> = None;
+
+    for token in stream {
+        let current = match &token {
+            TokenTree::Punct(punct) => Some(punct.as_char()),
+            _ => None,
+        };
+
+        match current {
+            Some(',') if depth == 0 => {
+                segments.push(CodeBlock::new());
+                previous = current;
+                continue;
+            }
+            Some('<') => depth += 1,
+            // the `>` of a `->` in a function type doesn't close a bracket
+            Some('>') if previous != Some('-') => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+
+        previous = current;
+        segments
+            .last_mut()
+            .expect("there is always a segment")
+            .push(token);
+    }
+
+    if segments.last().is_some_and(|segment| segment.is_empty()) {
+        segments.pop();
+    }
+
+    segments
+}
+
+fn value_text(value: &CodeBlock) -> String {
+    match value.as_slice() {
+        [TokenTree::Literal(literal)] => literal
+            .as_string_literal()
+            .unwrap_or_else(|_| literal.to_string()),
+        _ => value.iter().cloned().collect::<TokenStream>().to_string(),
+    }
+}
@@ -0,0 +1,144 @@
+use crate::{
+    code_block::*,
+    parse,
+    parser::*,
+    token_helpers::{ident, lit_string, parenthesised, punct},
+};
+
+/// The optional `$onfail "format", args` tail of an assertion, this isn't a rule in
+/// its own right but is fed the tokens of the assertion it is attached to.
+pub(crate) struct FailureMessage {
+    dollars: Option<Span>,
+    onfail: Option<Span>,
+    message: CodeBlock,
+    is_malformed: bool,
+}
+
+impl FailureMessage {
+    pub(crate) fn new() -> Self {
+        Self {
+            dollars: None,
+            onfail: None,
+            message: CodeBlock::new(),
+            is_malformed: false,
+        }
+    }
+
+    pub(crate) fn started(location: &impl SpanSource) -> Self {
+        Self {
+            onfail: Some(location.span()),
+            ..Self::new()
+        }
+    }
+
+    pub(crate) fn is_started(&self) -> bool {
+        self.onfail.is_some()
+    }
+
+    /// Takes any tokens that belong to the failure message, handing back those that
+    /// belong to the assertion itself.
+    pub(crate) fn accept_token(
+        &mut self,
+        token: TokenTree,
+        target: &mut SuiteGenerator,
+    ) -> Option<TokenTree> {
+        if self.is_started() {
+            self.message.push(token);
+            return None;
+        }
+
+        if let Some(dollars) = self.dollars.take() {
+            match &token {
+                TokenTree::Ident(ident)
+                    if parse::dollars_ident_match(ident.to_string())
+                        .is_ok_and(|d| matches!(d, parse::Dollars::OnFail)) =>
+                {
+                    self.onfail = Some(ident.span());
+                }
+                other => {
+                    self.is_malformed = true;
+                    target.push_new_error(
+                        &dollars,
+                        format!(
+                            "expected `{}` following the dollars inside an assertion, but found `{}`",
+                            parse::Dollars::OnFail,
+                            other
+                        ),
+                    );
+                }
+            }
+            return None;
+        }
+
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == '$' => {
+                self.dollars = Some(punct.span());
+                None
+            }
+            other => Some(other),
+        }
+    }
+
+    /// Validates the message once the assertion is complete, producing the tokens that
+    /// should follow the assertion's own arguments inside the assert macro.
+    pub(crate) fn arguments(&mut self, target: &mut SuiteGenerator) -> Result<CodeBlock, ()> {
+        if let Some(dollars) = self.dollars.take() {
+            target.push_new_error(
+                &dollars,
+                format!(
+                    "expected `{}` following the dollars inside an assertion, but found `;`",
+                    parse::Dollars::OnFail
+                ),
+            );
+            return Err(());
+        }
+
+        match self.onfail {
+            _ if self.is_malformed => Err(()),
+            None => Ok(CodeBlock::new()),
+            Some(onfail) if self.message.is_empty() => {
+                target.push_new_error(
+                    &onfail,
+                    "expected a failure message following onfail, but found `;`",
+                );
+                Err(())
+            }
+            Some(onfail) => {
+                let mut arguments = vec![punct(',', onfail)];
+                arguments.append(&mut self.message);
+                Ok(arguments)
+            }
+        }
+    }
+}
+
+/// The message arguments of an assertion that reports a summary line, followed by
+/// the lines of detail. A failure message from `$onfail` is added to the end of the
+/// summary. Like the failure message itself the arguments start with their comma.
+pub(crate) fn failure_report(
+    summary: (&str, CodeBlock),
+    detail: (&str, CodeBlock),
+    failure: CodeBlock,
+    sp: Span,
+) -> CodeBlock {
+    let (summary, mut summary_arguments) = summary;
+    let (detail, mut detail_arguments) = detail;
+
+    let format = match failure.is_empty() {
+        true => format!("{}\n{}", summary, detail),
+        false => format!("{}: {{}}\n{}", summary, detail),
+    };
+
+    let mut report = vec![punct(',', sp), lit_string(&format, sp)];
+    report.append(&mut summary_arguments);
+    if !failure.is_empty() {
+        report.extend([
+            punct(',', sp),
+            ident("format_args", sp),
+            punct('!', sp),
+            parenthesised(failure.into_iter().skip(1), sp),
+        ]);
+    }
+    report.append(&mut detail_arguments);
+    report
+}
@@ -0,0 +1,325 @@
+use crate::{
+    code_block::*,
+    name::*,
+    parse,
+    parser::*,
+    token_helpers::{Spacing, braced, code, ident, parenthesised, punct},
+};
+
+/// `$fork` which must then be followed by a braced block of tests.
+pub(crate) struct TransientFork {
+    parent: parse::AnchorParent,
+    anchor: Span,
+}
+
+impl TransientFork {
+    pub(crate) fn new(parent: parse::AnchorParent, location: &impl SpanSource) -> Self {
+        Self {
+            parent,
+            anchor: location.span(),
+        }
+    }
+}
+
+impl Parser for TransientFork {
+    fn accept_token(self, token: TokenTree, target: &mut SuiteGenerator) -> ParseRule {
+        match token {
+            TokenTree::Group(group) if group.delimiter() == Delimiter::Brace => {
+                ForkBlock::generate_block(self, group, target)
+            }
+
+            other => {
+                target.push_new_error(
+                    &other,
+                    format!(
+                        "expected a braced block of tests following `$fork`, but found `{}`",
+                        other
+                    ),
+                );
+                parse::TransientAssertError::new(self.parent).forward_token(other, target)
+            }
+        }
+    }
+
+    fn end_of_group(self, target: &mut SuiteGenerator) -> ParseRule {
+        target.push_new_error(
+            &self.anchor,
+            "reached end of group input before finding the block of tests for `$fork`",
+        );
+        self.parent.continuation()
+    }
+
+    fn end_of_stream(self, target: &mut SuiteGenerator) {
+        target.push_new_error(
+            &self.anchor,
+            "reached end of input before finding the block of tests for `$fork`",
+        );
+    }
+}
+
+/// A nameless block of tests whose code is run once, rather than by every test, and
+/// whose locals are then cloned into each of the tests inside it. Code written
+/// between the tests changes the state so the tests after it get a state of their own.
+pub(crate) struct ForkBlock {
+    parent: parse::AnchorParent,
+    location: Span,
+    code: parse::SharedCode,
+    /// The length of the code when its state was last numbered, and that number.
+    state: Option<(usize, usize)>,
+    has_children: bool,
+}
+
+impl ForkBlock {
+    fn generate_block(
+        transient: TransientFork,
+        group: Group,
+        target: &mut SuiteGenerator,
+    ) -> ParseRule {
+        let mut current_rule = ParseRule::ForkBlock(Self {
+            parent: transient.parent,
+            location: transient.anchor,
+            code: parse::SharedCode::new(),
+            state: None,
+            has_children: false,
+        });
+
+        for token in group.stream().into_iter() {
+            current_rule = current_rule.accept_token(token, target);
+        }
+
+        current_rule.end_of_group(target)
+    }
+
+    pub(crate) fn continuation(self) -> ParseRule {
+        ParseRule::ForkBlock(self)
+    }
+
+    pub(crate) fn shared_code(&mut self) -> &mut parse::SharedCode {
+        &mut self.code
+    }
+
+    /// `let (locals,) = spoke_fixtures::forked(state, || { code (locals,) });` which
+    /// runs the code on first use and gives every test its own clone of the locals.
+    fn forked_state(&self, state: usize) -> CodeBlock {
+        let location = self.location;
+        let locals = locals(self.code.code());
+
+        let mut returned: CodeBlock = self.code.code().clone();
+        returned.push(parenthesised(
+            locals
+                .iter()
+                .flat_map(|local| local.value().into_iter().chain([punct(',', location)]))
+                .collect::<CodeBlock>(),
+            location,
+        ));
+
+        let mut forked: CodeBlock = code("spoke_fixtures::forked", location)
+            .into_iter()
+            .collect();
+        forked.push(parenthesised(
+            code(&format!("{},", state), location)
+                .into_iter()
+                .chain(code("||", location))
+                .chain([braced(returned, location)]),
+            location,
+        ));
+
+        let mut binding = vec![ident("let", location)];
+        binding.push(parenthesised(
+            locals
+                .into_iter()
+                .flat_map(|local| local.binding().into_iter().chain([punct(',', location)]))
+                .collect::<CodeBlock>(),
+            location,
+        ));
+        binding.push(punct('=', location));
+        binding.extend(forked);
+        binding.push(punct(';', location));
+        binding
+    }
+}
+
+impl Parser for ForkBlock {
+    fn accept_token(mut self, token: TokenTree, target: &mut SuiteGenerator) -> ParseRule {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == '$' => {
+                let length = self.code.code().len();
+                if length > 0 && self.state.is_none_or(|(numbered, _)| numbered != length) {
+                    self.state = Some((length, target.number_shared_fixture()));
+                }
+
+                self.has_children = true;
+                parse::TransientBodyAnchor::new(parse::AnchorParent::from_fork(self), &punct)
+                    .consumed_token()
+            }
+
+            other => {
+                self.code.push(other);
+                self.consumed_token()
+            }
+        }
+    }
+
+    fn end_of_group(self, target: &mut SuiteGenerator) -> ParseRule {
+        if !self.has_children {
+            target.push_new_error(
+                &self.location,
+                "expected at least one test inside the `$fork` block",
+            );
+        }
+        self.parent.continuation()
+    }
+
+    fn end_of_stream(self, target: &mut SuiteGenerator) {
+        target.push_new_error(
+            &self.location,
+            "reached end of input before reaching the end of the `$fork` block",
+        );
+    }
+}
+
+impl Nameable for ForkBlock {
+    fn collect_name_parts<'a>(&'a self, compound: CompoundName<'a>) -> CompoundName<'a> {
+        self.parent.collect_name_parts(compound)
+    }
+}
+
+impl Populator for ForkBlock {
+    fn populate_test(&self, test: TestCase) -> TestCase {
+        let test = self.parent.populate_test(test);
+        match self.state {
+            Some((_, state)) => self.code.populate_forked(test, self.forked_state(state)),
+            None => self.code.populate_test(test),
+        }
+    }
+}
+
+/// A name bound by a `let` statement in the code, with the `ref` or `mut` it was
+/// bound with.
+struct Local {
+    name: Ident,
+    mode: CodeBlock,
+}
+
+impl Local {
+    /// The binding of the name in the tests, as it was bound in the code.
+    fn binding(self) -> CodeBlock {
+        let mut binding = self.mode;
+        binding.push(TokenTree::Ident(self.name));
+        binding
+    }
+
+    /// The value returned for the name, a name bound by `ref` borrows from the code
+    /// so its value is cloned instead.
+    fn value(&self) -> CodeBlock {
+        let mut value = vec![TokenTree::Ident(self.name.clone())];
+        if self
+            .mode
+            .first()
+            .is_some_and(|mode| mode.to_string() == "ref")
+        {
+            value.extend(code(".clone()", self.name.span()));
+        }
+        value
+    }
+}
+
+/// The names bound by the `let` statements in the code, a name that is shadowed by
+/// a later `let` is only bound by that later statement.
+fn locals(code: &CodeBlock) -> Vec<Local> {
+    let mut locals: Vec<Local> = Vec::new();
+    let mut pattern: Option<CodeBlock> = None;
+
+    for (index, token) in code.iter().enumerate() {
+        match (&mut pattern, token) {
+            (None, TokenTree::Ident(keyword))
+                if *keyword == "let" && starts_statement(code, index) =>
+            {
+                pattern = Some(CodeBlock::new())
+            }
+
+            (Some(_), TokenTree::Punct(punct))
+                if punct.as_char() == '='
+                    || punct.as_char() == ';'
+                    || (punct.as_char() == ':'
+                        && punct.spacing() == Spacing::Alone
+                        && !matches!(code.get(index - 1), Some(TokenTree::Punct(previous)) if previous.as_char() == ':')) =>
+            {
+                let pattern = pattern.take().expect("the pattern was started");
+                for local in bindings(&pattern) {
+                    locals.retain(|earlier| earlier.name != local.name);
+                    locals.push(local);
+                }
+            }
+
+            (Some(pattern), other) => pattern.push(other.clone()),
+
+            (None, _) => {}
+        }
+    }
+
+    locals
+}
+
+/// The names that a pattern binds, which leaves out the paths, types and field
+/// names that the pattern matches against.
+fn bindings(pattern: &[TokenTree]) -> Vec<Local> {
+    pattern
+        .iter()
+        .enumerate()
+        .flat_map(|(index, token)| match token {
+            TokenTree::Group(group) => bindings(&group.stream().into_iter().collect::<CodeBlock>()),
+            TokenTree::Ident(name) if is_binding(pattern, index) => vec![Local {
+                name: name.clone(),
+                mode: binding_mode(pattern, index),
+            }],
+            _ => Vec::new(),
+        })
+        .collect()
+}
+
+/// The `ref`, `mut` or `ref mut` in front of the name bound at the index.
+fn binding_mode(pattern: &[TokenTree], index: usize) -> CodeBlock {
+    let is_mode = |token: &&TokenTree| matches!(token, TokenTree::Ident(mode) if *mode == "ref" || *mode == "mut");
+    let length = pattern[..index]
+        .iter()
+        .rev()
+        .take(2)
+        .take_while(is_mode)
+        .count();
+    pattern[index - length..index].to_vec()
+}
+
+/// Whether the identifier binds a name, rather than being part of a path or a field
+/// name followed by the pattern for that field.
+fn is_binding(pattern: &[TokenTree], index: usize) -> bool {
+    let TokenTree::Ident(name) = &pattern[index] else {
+        return false;
+    };
+    let name = name.to_string();
+    let is_colon =
+        |token: &TokenTree| matches!(token, TokenTree::Punct(punct) if punct.as_char() == ':');
+    let is_path = |token: Option<&TokenTree>| match token {
+        Some(TokenTree::Punct(punct)) => punct.as_char() == ':' || punct.as_char() == '!',
+        Some(TokenTree::Group(_)) => true,
+        _ => false,
+    };
+
+    name != "mut"
+        && name != "ref"
+        && name != "_"
+        && name.starts_with(|first: char| first.is_lowercase() || first == '_')
+        && !is_path(pattern.get(index + 1))
+        && !(index >= 2 && is_colon(&pattern[index - 2]) && is_colon(&pattern[index - 1]))
+}
+
+/// Whether the token is at the start of a statement rather than in the condition
+/// of an `if let` or `while let`.
+fn starts_statement(code: &CodeBlock, index: usize) -> bool {
+    match index.checked_sub(1).and_then(|previous| code.get(previous)) {
+        None => true,
+        Some(TokenTree::Punct(punct)) => punct.as_char() == ';',
+        Some(TokenTree::Group(group)) => group.delimiter() == Delimiter::Brace,
+        Some(_) => false,
+    }
+}
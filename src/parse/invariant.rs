@@ -0,0 +1,132 @@
+use std::mem::take;
+
+use crate::{
+    code_block::*,
+    parse,
+    parser::*,
+    string_lit::TokenExtensions,
+    token_helpers::{TokenStream, lit_string, punct},
+};
+
+/// `$invariant "requirement" condition;`, a check which is made at the end of each
+/// of the tests that follow it in the body, after the test's own assertion.
+pub(crate) struct TransientInvariant {
+    parent: parse::AnchorParent,
+    anchor: Span,
+    requirement: Option<(Span, String)>,
+    condition: CodeBlock,
+}
+
+impl TransientInvariant {
+    pub(crate) fn new(parent: parse::AnchorParent, location: &impl SpanSource) -> Self {
+        Self {
+            parent,
+            anchor: location.span(),
+            requirement: None,
+            condition: CodeBlock::new(),
+        }
+    }
+
+    fn accept_requirement(self, token: TokenTree, target: &mut SuiteGenerator) -> ParseRule {
+        if let TokenTree::Literal(literal) = &token
+            && let Ok(requirement) = literal.as_string_literal()
+        {
+            return Self {
+                requirement: Some((literal.span(), requirement)),
+                ..self
+            }
+            .consumed_token();
+        }
+
+        target.push_new_error(
+            &token,
+            format!(
+                "expected a requirement in quotes following `$invariant`, but found `{}`",
+                token
+            ),
+        );
+        parse::TransientAssertError::new(self.parent).forward_token(token, target)
+    }
+
+    fn add_check(&mut self, target: &mut SuiteGenerator) {
+        let Some((location, requirement)) = self.requirement.take() else {
+            return;
+        };
+
+        let condition = take(&mut self.condition);
+        if condition.is_empty() {
+            target.push_new_error(
+                &location,
+                "expected a condition following the requirement of the `$invariant`, but found `;`",
+            );
+            return;
+        }
+
+        let expression = condition
+            .iter()
+            .cloned()
+            .collect::<TokenStream>()
+            .to_string();
+        let check = target.backend().assert(
+            condition,
+            [
+                punct(',', location),
+                lit_string("invariant `{}` does not hold: {}", location),
+                punct(',', location),
+                lit_string(&requirement, location),
+                punct(',', location),
+                lit_string(&expression, location),
+            ],
+            location,
+        );
+
+        if let Some(shared) =
+            parse::enclosing_code(&mut self.parent, "invariant", &self.anchor, target)
+        {
+            shared.push_invariant(check);
+        }
+    }
+}
+
+impl Parser for TransientInvariant {
+    fn accept_token(mut self, token: TokenTree, target: &mut SuiteGenerator) -> ParseRule {
+        if self.requirement.is_none() {
+            return self.accept_requirement(token, target);
+        }
+
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == ';' => {
+                self.add_check(target);
+                self.parent.continuation()
+            }
+
+            TokenTree::Punct(punct) if punct.as_char() == '$' => {
+                target.push_new_error(
+                    &punct,
+                    "an `$invariant` is a plain condition so it can't be followed by further dollars",
+                );
+                parse::TransientAssertError::new(self.parent).consumed_token()
+            }
+
+            other => {
+                self.condition.push(other);
+                self.consumed_token()
+            }
+        }
+    }
+
+    fn end_of_group(self, target: &mut SuiteGenerator) -> ParseRule {
+        target.push_new_error(
+            &self.anchor,
+            "reached end of group input before reaching the end of the `$invariant`. Missing ; ?",
+        );
+        self.parent.continuation()
+    }
+
+    fn end_of_stream(self, target: &mut SuiteGenerator) {
+        target.push_new_error(
+            &self.anchor,
+            "reached end of input before reaching the end of the `$invariant`. Missing ; ?",
+        );
+    }
+}
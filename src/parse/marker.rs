@@ -0,0 +1,210 @@
+use std::fmt::Display;
+
+use crate::{name::*, parse, parser::*, string_lit::TokenExtensions};
+
+/// Markers prefix a test definition and alter every test generated beneath it,
+/// they don't contribute to the name of the test.
+pub(crate) enum Marker {
+    Ignore,
+    Pending,
+}
+
+impl Marker {
+    fn accepts_argument(&self, argument: &TokenTree) -> bool {
+        match self {
+            Marker::Ignore => {
+                matches!(argument, TokenTree::Literal(reason) if reason.as_string_literal().is_ok())
+            }
+            Marker::Pending => match argument {
+                TokenTree::Ident(_) => true,
+                TokenTree::Literal(feature) => feature.as_string_literal().is_ok(),
+                _ => false,
+            },
+        }
+    }
+
+    fn argument_description(&self) -> &'static str {
+        match self {
+            Marker::Ignore => "a reason in quotes",
+            Marker::Pending => "a feature name",
+        }
+    }
+}
+
+impl Display for Marker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Marker::Ignore => write!(f, "ignore"),
+            Marker::Pending => write!(f, "pending"),
+        }
+    }
+}
+
+pub(crate) fn marker_ident_match(ident: String) -> Result<Marker, String> {
+    match ident.as_str() {
+        "ignore" => Result::Ok(Marker::Ignore),
+        "pending" => Result::Ok(Marker::Pending),
+        other => Result::Err(other.to_string()),
+    }
+}
+
+pub(crate) struct TransientMarker {
+    parent: parse::AnchorParent,
+    marker: Marker,
+    anchor: Span,
+    argument: Option<TokenTree>,
+    expecting_argument: bool,
+}
+
+impl TransientMarker {
+    pub(crate) fn new(
+        parent: parse::AnchorParent,
+        marker: Marker,
+        location: &impl SpanSource,
+    ) -> Self {
+        Self {
+            parent,
+            marker,
+            anchor: location.span(),
+            argument: None,
+            expecting_argument: false,
+        }
+    }
+
+    fn marked_parent(self) -> parse::AnchorParent {
+        parse::AnchorParent::from_marked(Marked {
+            parent: self.parent,
+            marker: self.marker,
+            location: self.anchor,
+            argument: self.argument,
+        })
+    }
+
+    fn expected_name(self, token: TokenTree, target: &mut SuiteGenerator) -> ParseRule {
+        target.push_new_error(
+            &token,
+            format!(
+                "expected a test name in quotes following the {} marker, but found `{}`",
+                self.marker, token
+            ),
+        );
+
+        let name = target.make_missing_name(&token);
+        parse::TransientBodyNamingError::new(self.marked_parent(), name)
+            .forward_token(token, target)
+    }
+
+    fn accept_argument(mut self, token: TokenTree, target: &mut SuiteGenerator) -> ParseRule {
+        self.expecting_argument = false;
+
+        if self.marker.accepts_argument(&token) {
+            self.argument = Some(token);
+        } else {
+            target.push_new_error(
+                &token,
+                format!(
+                    "expected {} following `{}.`, but found `{}`",
+                    self.marker.argument_description(),
+                    self.marker,
+                    token
+                ),
+            );
+        }
+
+        self.consumed_token()
+    }
+}
+
+impl Parser for TransientMarker {
+    fn accept_token(mut self, token: TokenTree, target: &mut SuiteGenerator) -> ParseRule {
+        if self.expecting_argument {
+            return self.accept_argument(token, target);
+        }
+
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == '.' && self.argument.is_none() => {
+                self.expecting_argument = true;
+                self.consumed_token()
+            }
+
+            TokenTree::Punct(punct) if punct.as_char() == '$' => {
+                parse::TransientBodyAnchor::new(self.marked_parent(), &punct).consumed_token()
+            }
+
+            TokenTree::Literal(literal) => match literal.as_string_literal() {
+                Ok(name) => {
+                    parse::TransientBodyNamed::new(self.marked_parent(), Name::new(&literal, name))
+                        .consumed_token()
+                }
+                Err(_) => self.expected_name(TokenTree::Literal(literal), target),
+            },
+
+            other => self.expected_name(other, target),
+        }
+    }
+
+    fn end_of_group(self, target: &mut SuiteGenerator) -> ParseRule {
+        target.push_new_error(
+            &self.anchor,
+            "reached end of group input before reaching the end of the test definition",
+        );
+        self.parent.continuation()
+    }
+
+    fn end_of_stream(self, target: &mut SuiteGenerator) {
+        target.push_new_error(
+            &self.anchor,
+            "reached end of input before reaching the end of the test definition",
+        );
+    }
+}
+
+/// The parent of a marked test definition, applies the marker to each test beneath it.
+pub(crate) struct Marked {
+    parent: parse::AnchorParent,
+    marker: Marker,
+    location: Span,
+    argument: Option<TokenTree>,
+}
+
+impl Marked {
+    pub(crate) fn continuation(self) -> ParseRule {
+        self.parent.continuation()
+    }
+}
+
+impl Nameable for Marked {
+    fn collect_name_parts<'a>(&'a self, compound: CompoundName<'a>) -> CompoundName<'a> {
+        self.parent.collect_name_parts(compound)
+    }
+}
+
+impl Populator for Marked {
+    fn populate_test(&self, test: TestCase) -> TestCase {
+        let mut test = self.parent.populate_test(test);
+
+        match self.marker {
+            Marker::Ignore => test.ignore(
+                &self.location,
+                match &self.argument {
+                    Some(TokenTree::Literal(reason)) => Some(reason.clone()),
+                    _ => None,
+                },
+            ),
+            Marker::Pending => test.pending(
+                &self.location,
+                match &self.argument {
+                    Some(TokenTree::Ident(feature)) => {
+                        let mut feature_name = Literal::string(&feature.to_string());
+                        feature_name.set_span(feature.span());
+                        Some(feature_name)
+                    }
+                    Some(TokenTree::Literal(feature)) => Some(feature.clone()),
+                    _ => None,
+                },
+            ),
+        }
+
+        test
+    }
+}
@@ -24,3 +24,57 @@ pub(crate) use assert::*;
 
 mod assert_eq;
 pub(crate) use assert_eq::*;
+
+mod data_table;
+pub(crate) use data_table::*;
+
+mod auto_named;
+pub(crate) use auto_named::*;
+
+mod marker;
+pub(crate) use marker::*;
+
+mod failure_message;
+pub(crate) use failure_message::*;
+
+mod assert_panics;
+pub(crate) use assert_panics::*;
+
+mod assert_variant;
+pub(crate) use assert_variant::*;
+
+mod assert_matches;
+pub(crate) use assert_matches::*;
+
+mod assert_approx;
+pub(crate) use assert_approx::*;
+
+mod assert_collection;
+pub(crate) use assert_collection::*;
+
+mod assert_const;
+pub(crate) use assert_const::*;
+
+mod assert_type;
+pub(crate) use assert_type::*;
+
+mod config;
+pub(crate) use config::*;
+
+mod async_block;
+pub(crate) use async_block::*;
+
+mod fork_block;
+pub(crate) use fork_block::*;
+
+mod shared_code;
+pub(crate) use shared_code::*;
+
+mod after;
+pub(crate) use after::*;
+
+mod invariant;
+pub(crate) use invariant::*;
+
+mod shared_fixture;
+pub(crate) use shared_fixture::*;
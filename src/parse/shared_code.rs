@@ -0,0 +1,104 @@
+use crate::{code_block::*, name::*, parse, parser::*, token_helpers::IterableTokens};
+
+/// The code of a body that is shared by the tests beneath it, along with any
+/// `$after` blocks and the point in the code at which each was written, and the
+/// checks of any `$invariant`s.
+pub(crate) struct SharedCode {
+    code: CodeBlock,
+    teardowns: Vec<(usize, Span, CodeBlock)>,
+    invariants: CodeBlock,
+}
+
+impl SharedCode {
+    pub(crate) fn new() -> Self {
+        Self {
+            code: CodeBlock::new(),
+            teardowns: Vec::new(),
+            invariants: CodeBlock::new(),
+        }
+    }
+
+    pub(crate) fn push(&mut self, token: TokenTree) {
+        self.code.push(token);
+    }
+
+    pub(crate) fn extend(&mut self, code: impl IterableTokens) {
+        self.code.extend(code);
+    }
+
+    pub(crate) fn push_teardown(&mut self, location: Span, code: CodeBlock) {
+        self.teardowns.push((self.code.len(), location, code));
+    }
+
+    pub(crate) fn push_invariant(&mut self, check: CodeBlock) {
+        self.invariants.extend(check);
+    }
+
+    pub(crate) fn code(&self) -> &CodeBlock {
+        &self.code
+    }
+
+    /// Populates a test whose code has already been run once by a `$fork` block,
+    /// so any `$after` blocks follow the `state` that the code was replaced with.
+    pub(crate) fn populate_forked(
+        &self,
+        mut test: TestCase,
+        state: impl IterableTokens,
+    ) -> TestCase {
+        test.push_code(state);
+        for (_, location, teardown) in &self.teardowns {
+            test.push_teardown(location, teardown.clone());
+        }
+        test.push_invariant(self.invariants.clone());
+        test
+    }
+}
+
+impl Populator for SharedCode {
+    fn populate_test(&self, mut test: TestCase) -> TestCase {
+        let mut start = 0;
+        for (position, location, teardown) in &self.teardowns {
+            test.push_code(self.code[start..*position].iter().cloned());
+            test.push_teardown(location, teardown.clone());
+            start = *position;
+        }
+        test.push_code(self.code[start..].iter().cloned());
+        test.push_invariant(self.invariants.clone());
+        test
+    }
+}
+
+/// The shared code of the body that `$after` or `$invariant` was written in. Both
+/// apply to every test that follows them in the body so they can't be marked.
+pub(crate) fn enclosing_code<'a>(
+    parent: &'a mut parse::AnchorParent,
+    dollars: &str,
+    location: &impl SpanSource,
+    target: &mut SuiteGenerator,
+) -> Option<&'a mut SharedCode> {
+    match parent {
+        parse::AnchorParent::Body(body) => Some(body.shared_code()),
+        parse::AnchorParent::Async(block) => Some(block.shared_code()),
+        parse::AnchorParent::Fork(block) => Some(block.shared_code()),
+        parse::AnchorParent::Marked(_) => {
+            target.push_new_error(
+                location,
+                format!(
+                    "`${}` applies to all of the tests that follow it so it can't be marked",
+                    dollars
+                ),
+            );
+            None
+        }
+        parse::AnchorParent::Suite(_) | parse::AnchorParent::Row(_) => {
+            target.push_new_error(
+                location,
+                format!(
+                    "`${}` is only allowed inside the braced body of a test",
+                    dollars
+                ),
+            );
+            None
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use std::mem::take;
+
+use crate::{
+    code_block::*,
+    parse,
+    parser::*,
+    token_helpers::{Spacing, braced, code, ident, parenthesised, punct},
+};
+
+/// `$shared let name = expression;`, a fixture which is created once and then
+/// borrowed by each of the tests that follow it, rather than being created again
+/// by every test. The expression can't use any of the surrounding setup code.
+pub(crate) struct TransientSharedFixture {
+    parent: parse::AnchorParent,
+    anchor: Span,
+    has_let: bool,
+    pattern: CodeBlock,
+    fixture_type: Option<CodeBlock>,
+    expression: Option<CodeBlock>,
+}
+
+impl TransientSharedFixture {
+    pub(crate) fn new(parent: parse::AnchorParent, location: &impl SpanSource) -> Self {
+        Self {
+            parent,
+            anchor: location.span(),
+            has_let: false,
+            pattern: CodeBlock::new(),
+            fixture_type: None,
+            expression: None,
+        }
+    }
+
+    fn accept_let(mut self, token: TokenTree, target: &mut SuiteGenerator) -> ParseRule {
+        if let TokenTree::Ident(ident) = &token
+            && *ident == "let"
+        {
+            self.has_let = true;
+            return self.consumed_token();
+        }
+
+        target.push_new_error(
+            &token,
+            format!("expected `let` following `$shared`, but found `{}`", token),
+        );
+        parse::TransientAssertError::new(self.parent).forward_token(token, target)
+    }
+
+    /// `let pattern = spoke_fixtures::shared(fixture, || expression);` or with the
+    /// type given to `shared` when the fixture has one.
+    fn generate_binding(&mut self, target: &mut SuiteGenerator) {
+        let location = self.anchor;
+        let pattern = take(&mut self.pattern);
+        let expression = self.expression.take().unwrap_or_default();
+
+        if pattern.is_empty() {
+            target.push_new_error(&location, "expected a name following `$shared let`");
+            return;
+        }
+
+        if expression.is_empty() {
+            target.push_new_error(
+                &location,
+                "expected `=` and an expression following the name of the `$shared` fixture",
+            );
+            return;
+        }
+
+        let mut shared: CodeBlock = code("spoke_fixtures::shared", location)
+            .into_iter()
+            .collect();
+        if let Some(fixture_type) = self.fixture_type.take() {
+            shared.extend(code("::<", location));
+            shared.extend(fixture_type);
+            shared.push(punct('>', location));
+        }
+        shared.push(parenthesised(
+            code(&format!("{},", target.number_shared_fixture()), location)
+                .into_iter()
+                .chain(code("||", location))
+                .chain([braced(expression, location)]),
+            location,
+        ));
+
+        let mut binding = vec![ident("let", location)];
+        binding.extend(pattern);
+        binding.push(punct('=', location));
+        binding.extend(shared);
+        binding.push(punct(';', location));
+
+        match &mut self.parent {
+            parse::AnchorParent::Suite(_) if target.has_tests() => target.push_new_error(
+                &location,
+                "`$shared` is only allowed in the suite preamble, before any tests are defined, or in the body of a test",
+            ),
+            parse::AnchorParent::Suite(_) => target.push_shared_fixture(binding),
+            parent => {
+                if let Some(shared) = parse::enclosing_code(parent, "shared", &location, target) {
+                    shared.extend(binding);
+                }
+            }
+        }
+    }
+}
+
+impl Parser for TransientSharedFixture {
+    fn accept_token(mut self, token: TokenTree, target: &mut SuiteGenerator) -> ParseRule {
+        if !self.has_let {
+            return self.accept_let(token, target);
+        }
+
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == ';' => {
+                self.generate_binding(target);
+                self.parent.continuation()
+            }
+
+            TokenTree::Punct(punct) if punct.as_char() == '$' => {
+                target.push_new_error(
+                    &punct,
+                    "a `$shared` fixture is created once for all of its tests so it can't be followed by further dollars",
+                );
+                parse::TransientAssertError::new(self.parent).consumed_token()
+            }
+
+            TokenTree::Punct(punct) if punct.as_char() == '=' && self.expression.is_none() => {
+                self.expression = Some(CodeBlock::new());
+                self.consumed_token()
+            }
+
+            // a single `:` rather than a path separator in the pattern
+            TokenTree::Punct(punct)
+                if punct.as_char() == ':'
+                    && punct.spacing() == Spacing::Alone
+                    && !matches!(self.pattern.last(), Some(TokenTree::Punct(previous)) if previous.as_char() == ':')
+                    && self.expression.is_none()
+                    && self.fixture_type.is_none() =>
+            {
+                self.fixture_type = Some(CodeBlock::new());
+                self.consumed_token()
+            }
+
+            other => {
+                match (&mut self.expression, &mut self.fixture_type) {
+                    (Some(expression), _) => expression.push(other),
+                    (None, Some(fixture_type)) => fixture_type.push(other),
+                    (None, None) => self.pattern.push(other),
+                }
+                self.consumed_token()
+            }
+        }
+    }
+
+    fn end_of_group(self, target: &mut SuiteGenerator) -> ParseRule {
+        target.push_new_error(
+            &self.anchor,
+            "reached end of group input before reaching the end of the `$shared` fixture. Missing ; ?",
+        );
+        self.parent.continuation()
+    }
+
+    fn end_of_stream(self, target: &mut SuiteGenerator) {
+        target.push_new_error(
+            &self.anchor,
+            "reached end of input before reaching the end of the `$shared` fixture. Missing ; ?",
+        );
+    }
+}
@@ -64,4 +64,24 @@ impl_rule!(
     TransientAssertAnchor,
     TransientAssertError,
     AssertEq,
+    AssertPanics,
+    AssertVariant,
+    AssertMatches,
+    AssertApprox,
+    AssertCollection,
+    ConstAssert,
+    AssertType,
+    DataTable,
+    DataTableNamed,
+    DataRow,
+    TransientMarker,
+    TransientAutoNamed,
+    TransientConfig,
+    TransientAsync,
+    AsyncBlock,
+    TransientFork,
+    ForkBlock,
+    TransientAfter,
+    TransientInvariant,
+    TransientSharedFixture,
 );
@@ -0,0 +1,380 @@
+use crate::{code_block::*, token_helpers::*};
+
+/// A plain assertion taken apart at its comparisons, `&&`, `||` and `!` so that the
+/// value of every part that was evaluated can be reported when it fails.
+pub(crate) enum Expression {
+    Or(Vec<Expression>),
+    And(Vec<Expression>),
+    Not(Box<Expression>),
+    Compare(CodeBlock, String, CodeBlock),
+    Part(CodeBlock),
+}
+
+/// Rust's operators that are written with more than one character, longest first so
+/// that `<<=` isn't read as `<<` or `<`.
+const OPERATORS: [&str; 24] = [
+    "<<=", ">>=", "...", "..=", "::", "==", "!=", "<=", ">=", "&&", "||", "<<", ">>", "+=", "-=",
+    "*=", "/=", "%=", "^=", "&=", "|=", "->", "=>", "..",
+];
+
+/// Words that start an expression whose extent can't be found from its tokens alone.
+const UNSUPPORTED_KEYWORDS: [&str; 11] = [
+    "if", "match", "loop", "while", "for", "let", "return", "break", "continue", "move", "yield",
+];
+
+impl Expression {
+    /// Takes apart an expression with a comparison, `&&` or `||` at its top level,
+    /// anything else is left to a plain `assert!`.
+    pub(crate) fn decompose(tokens: &[TokenTree]) -> Option<Self> {
+        Self::parse(tokens).filter(Self::has_operator)
+    }
+
+    fn has_operator(&self) -> bool {
+        match self {
+            Expression::Part(_) => false,
+            Expression::Not(inner) => inner.has_operator(),
+            _ => true,
+        }
+    }
+
+    fn parse(tokens: &[TokenTree]) -> Option<Self> {
+        let operators = binary_operators(tokens)?;
+
+        for joiner in ["||", "&&"] {
+            let mut parts = Vec::new();
+            let mut start = 0;
+            for (index, length, _) in operators.iter().filter(|(_, _, op)| op == joiner) {
+                parts.push(Self::parse(&tokens[start..*index])?);
+                start = index + length;
+            }
+            if !parts.is_empty() {
+                parts.push(Self::parse(&tokens[start..])?);
+                return Some(match joiner {
+                    "||" => Expression::Or(parts),
+                    _ => Expression::And(parts),
+                });
+            }
+        }
+
+        match operators.as_slice() {
+            [] => Self::parse_unary(tokens),
+            [(index, length, comparison)] if index + length < tokens.len() => {
+                Some(Expression::Compare(
+                    tokens[..*index].to_vec(),
+                    comparison.clone(),
+                    tokens[index + length..].to_vec(),
+                ))
+            }
+            // rust doesn't allow comparisons to be chained
+            _ => None,
+        }
+    }
+
+    fn parse_unary(tokens: &[TokenTree]) -> Option<Self> {
+        match tokens {
+            [] => None,
+            [TokenTree::Group(group)] if group.delimiter() == Delimiter::Parenthesis => {
+                Self::parse(&group.stream().into_iter().collect::<CodeBlock>())
+            }
+            [TokenTree::Punct(punct), rest @ ..] if punct.as_char() == '!' => {
+                Some(Expression::Not(Box::new(Self::parse_unary(rest)?)))
+            }
+            _ => Some(Expression::Part(tokens.to_vec())),
+        }
+    }
+
+    /// An expression with the same value and short circuiting as the original, which
+    /// also records each part it evaluates into `spoke_values`.
+    pub(crate) fn generate(self, sp: Span) -> CodeBlock {
+        match self {
+            Expression::Or(parts) => join(parts, "||", sp),
+            Expression::And(parts) => join(parts, "&&", sp),
+            Expression::Not(inner) => vec![punct('!', sp), parenthesised(inner.generate(sp), sp)],
+            Expression::Compare(left, comparison, right) => {
+                let text = format!("{} {} {}", as_text(&left), comparison, as_text(&right));
+
+                let mut output: CodeBlock = code("match", sp).into_iter().collect();
+                output.push(parenthesised(
+                    [punct('&', sp), parenthesised(left, sp), punct(',', sp)]
+                        .into_iter()
+                        .chain([punct('&', sp), parenthesised(right, sp)]),
+                    sp,
+                ));
+
+                let mut values = vec![lit_string(&format!("{{}} {} {{}}", comparison), sp)];
+                values.push(punct(',', sp));
+                values.extend(described(code("spoke_left", sp), sp));
+                values.push(punct(',', sp));
+                values.extend(described(code("spoke_right", sp), sp));
+
+                let mut arm = record(
+                    &text,
+                    code("format!", sp)
+                        .into_iter()
+                        .chain([parenthesised(values, sp)]),
+                    sp,
+                );
+                arm.extend(code(
+                    &format!("*spoke_left {} *spoke_right", comparison),
+                    sp,
+                ));
+
+                output.push(braced(
+                    code("(spoke_left, spoke_right) =>", sp)
+                        .into_iter()
+                        .chain([braced(arm, sp)]),
+                    sp,
+                ));
+                output
+            }
+            Expression::Part(tokens) => {
+                let mut output = match receiver(&tokens) {
+                    Some(receiver) => record(
+                        &as_text(receiver),
+                        described(
+                            [punct('&', sp)].into_iter().chain(receiver.iter().cloned()),
+                            sp,
+                        ),
+                        sp,
+                    ),
+                    None => CodeBlock::new(),
+                };
+
+                let mut arm = record(
+                    &as_text(&tokens),
+                    code("format!(\"{:?}\", spoke_value)", sp),
+                    sp,
+                );
+                arm.extend(code("spoke_value", sp));
+
+                output.extend(code("match", sp));
+                output.push(invisible(tokens, sp));
+                output.push(braced(
+                    code("spoke_value =>", sp)
+                        .into_iter()
+                        .chain([braced(arm, sp)]),
+                    sp,
+                ));
+                vec![braced(output, sp)]
+            }
+        }
+    }
+}
+
+/// The top level binary operators as `(index, length, operator)`, or `None` when the
+/// expression holds something such as a closure, a range or an `if` that would make
+/// splitting it at those operators unreliable.
+fn binary_operators(tokens: &[TokenTree]) -> Option<Vec<(usize, usize, String)>> {
+    let mut operators = Vec::new();
+    let mut expects_operand = true;
+    let mut generics = 0;
+    let mut index = 0;
+
+    while let Some(token) = tokens.get(index) {
+        match token {
+            TokenTree::Ident(ident) => {
+                if UNSUPPORTED_KEYWORDS.iter().any(|keyword| ident == keyword) {
+                    return None;
+                }
+                expects_operand = false;
+                index += 1;
+            }
+
+            TokenTree::Punct(_) => {
+                let (operator, length) = operator_at(tokens, index);
+
+                if generics > 0 {
+                    // the arguments of a turbofish, where `<` and `>` are brackets
+                    if operator != "->" {
+                        for c in operator.chars() {
+                            match c {
+                                '<' => generics += 1,
+                                '>' => generics -= 1,
+                                _ => (),
+                            }
+                        }
+                    }
+                    index += length;
+                    continue;
+                }
+
+                match operator.as_str() {
+                    "::" if matches!(tokens.get(index + length), Some(TokenTree::Punct(punct)) if punct.as_char() == '<') =>
+                    {
+                        generics = 1;
+                        index += 1;
+                    }
+                    "||" | "&&" | "==" | "!=" | "<" | "<=" | ">" | ">=" if !expects_operand => {
+                        operators.push((index, length, operator.clone()));
+                        expects_operand = true;
+                    }
+                    // a closure or a qualified path
+                    "|" | "||" | "<" | "<<" => return None,
+                    "?" => (),
+                    "=" | "+=" | "-=" | "*=" | "/=" | "%=" | "^=" | "&=" | "|=" | "<<=" | ">>="
+                    | ".." | "..=" | "..." | "=>" | "->" | "," | ";" | "'" => return None,
+                    _ => expects_operand = true,
+                }
+                index += length;
+            }
+
+            _ => {
+                expects_operand = false;
+                index += 1;
+            }
+        }
+    }
+
+    Some(operators)
+}
+
+/// The operator starting at `index` and the number of tokens that make it up.
+fn operator_at(tokens: &[TokenTree], index: usize) -> (String, usize) {
+    let mut joined = String::new();
+    for token in &tokens[index..] {
+        let TokenTree::Punct(punct) = token else {
+            break;
+        };
+        joined.push(punct.as_char());
+        if punct.spacing() == Spacing::Alone {
+            break;
+        }
+    }
+
+    match OPERATORS
+        .iter()
+        .find(|operator| joined.starts_with(**operator))
+    {
+        Some(operator) => (operator.to_string(), operator.len()),
+        None => (joined[..1].to_string(), 1),
+    }
+}
+
+/// The variable or field that a method is called on, such as `cart.items` in
+/// `cart.items.is_empty()`, which can be borrowed again to report its value.
+fn receiver(tokens: &[TokenTree]) -> Option<&[TokenTree]> {
+    let [
+        place @ ..,
+        TokenTree::Punct(dot),
+        TokenTree::Ident(_),
+        TokenTree::Group(args),
+    ] = tokens
+    else {
+        return None;
+    };
+    if dot.as_char() != '.' || args.delimiter() != Delimiter::Parenthesis {
+        return None;
+    }
+
+    let [TokenTree::Ident(_), fields @ ..] = place else {
+        return None;
+    };
+    let is_place = fields.chunks(2).all(|field| {
+        matches!(field, [TokenTree::Punct(dot), TokenTree::Ident(_) | TokenTree::Literal(_)] if dot.as_char() == '.')
+    });
+
+    is_place.then_some(place)
+}
+
+fn join(parts: Vec<Expression>, joiner: &str, sp: Span) -> CodeBlock {
+    let mut output = CodeBlock::new();
+    for part in parts {
+        if !output.is_empty() {
+            output.extend(code(joiner, sp));
+        }
+        output.push(parenthesised(part.generate(sp), sp));
+    }
+    output
+}
+
+/// `spoke_values.record("text", value);`
+fn record(text: &str, value: impl IterableTokens, sp: Span) -> CodeBlock {
+    let mut output: CodeBlock = code("spoke_values.record", sp).into_iter().collect();
+    output.push(parenthesised(
+        [lit_string(text, sp), punct(',', sp)]
+            .into_iter()
+            .chain(value),
+        sp,
+    ));
+    output.push(punct(';', sp));
+    output
+}
+
+/// `(&spoke_power::Value(value)).spoke_debug()`, which falls back to the name of the
+/// type when the value isn't `Debug`.
+fn described(value: impl IterableTokens, sp: Span) -> CodeBlock {
+    let mut output = vec![parenthesised(
+        code("&spoke_power::Value", sp)
+            .into_iter()
+            .chain([parenthesised(value, sp)]),
+        sp,
+    )];
+    output.extend(code(".spoke_debug()", sp));
+    output
+}
+
+fn as_text(tokens: &[TokenTree]) -> String {
+    tokens.iter().cloned().collect::<TokenStream>().to_string()
+}
+
+/// The helpers that decomposed assertions call at runtime, generated once into any
+/// suite that uses them. A part of the expression is described by the `ViaDebug`
+/// method on `Value` when it is `Debug`, and by the `WithoutDebug` method on
+/// `&Value` otherwise, so that the assertion compiles whatever the types.
+pub(crate) fn support_module(sp: Span) -> TokenStream {
+    code(SUPPORT_MODULE, sp)
+}
+
+const SUPPORT_MODULE: &str = r#"
+#[allow(dead_code)]
+mod spoke_power {
+    use ::std::fmt::{Debug, Display, Formatter};
+
+    /// The text and value of each part of the expression, in the order evaluated.
+    pub struct Values(Vec<(&'static str, String)>);
+
+    impl Values {
+        pub fn new() -> Self {
+            Values(Vec::new())
+        }
+
+        pub fn record(&mut self, expression: &'static str, value: String) {
+            self.0.push((expression, value));
+        }
+    }
+
+    impl Display for Values {
+        fn fmt(&self, f: &mut Formatter<'_>) -> ::std::fmt::Result {
+            for (index, (expression, value)) in self.0.iter().enumerate() {
+                if index > 0 {
+                    writeln!(f)?;
+                }
+                write!(f, "  {} → {}", expression, value)?;
+            }
+            Ok(())
+        }
+    }
+
+    pub struct Value<'a, T: ?Sized>(pub &'a T);
+
+    pub trait ViaDebug {
+        fn spoke_debug(&self) -> String;
+    }
+
+    impl<T: Debug + ?Sized> ViaDebug for Value<'_, T> {
+        fn spoke_debug(&self) -> String {
+            format!("{:?}", self.0)
+        }
+    }
+
+    pub trait WithoutDebug {
+        fn spoke_debug(&self) -> String;
+    }
+
+    impl<T: ?Sized> WithoutDebug for &Value<'_, T> {
+        fn spoke_debug(&self) -> String {
+            format!("<{}>", ::std::any::type_name::<T>())
+        }
+    }
+}
+"#;
@@ -2,6 +2,7 @@ use crate::{parse, parser::*};
 
 pub(crate) fn generate_tests(input: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
     let mut suite = SuiteGenerator::new();
+    suite.fingerprint_input(&input);
 
     if !input.is_empty() {
         let mut current_rule = ParseRule::Suite(parse::Suite());
@@ -20,17 +20,17 @@ fn string_as_string_literal(input: impl AsRef<str>) -> Result<String, String> {
 
     loop {
         if chars.len() < 2 {
-            return Err(format!("string too short"));
+            return Err("string too short".to_string());
         }
 
         match chars.pop_front() {
-            None => return Err(format!("no first character")),
+            None => return Err("no first character".to_string()),
 
             Some('r') => {
                 if chars.len() + 1 == full_len {
                     is_raw = true;
                 } else {
-                    return Err(format!("found r at a position other than the start"));
+                    return Err("found r at a position other than the start".to_string());
                 }
             }
 
@@ -56,9 +56,9 @@ fn string_as_string_literal(input: impl AsRef<str>) -> Result<String, String> {
                             c => suffix.insert(0, c),
                         }
                     }
-                    return Err(format!("missing closing quote on string"));
+                    return Err("missing closing quote on string".to_string());
                 }
-                None => return Err(format!("unbalanced surrounding quotes")),
+                None => return Err("unbalanced surrounding quotes".to_string()),
             },
 
             Some('#') if is_raw => match chars.pop_back() {
@@ -77,16 +77,16 @@ fn string_as_string_literal(input: impl AsRef<str>) -> Result<String, String> {
                                         suffix
                                     ));
                                 } else {
-                                    return Err(format!("bad raw string format"));
+                                    return Err("bad raw string format".to_string());
                                 }
                             }
                             c => suffix.insert(0, c),
                         }
                     }
-                    return Err(format!("missing closing hash on raw string"));
+                    return Err("missing closing hash on raw string".to_string());
                 }
                 None => {
-                    return Err(format!("unbalanced surrounding hashes"));
+                    return Err("unbalanced surrounding hashes".to_string());
                 }
             },
 
@@ -110,7 +110,7 @@ fn verify_quoted(chars: VecDeque<char>) -> Result<String, String> {
         result.push(c);
     }
 
-    return Ok(result);
+    Ok(result)
 }
 
 #[cfg(test)]
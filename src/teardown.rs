@@ -0,0 +1,50 @@
+use crate::token_helpers::*;
+
+/// The wrapper that lets an async test run its `$after` blocks, check a `$panics`
+/// assertion or pass while `$pending`, when it panics, generated once into any suite
+/// with such a test. The test's future is polled inside `catch_unwind` so a panic is returned
+/// as the output of the future rather than unwinding through the runtime.
+pub(crate) fn support_module(sp: Span) -> TokenStream {
+    code(SUPPORT_MODULE, sp)
+}
+
+/// The async counterpart of `catch_unwind`, awaiting the body as a future whose
+/// output is the result of running it.
+pub(crate) fn catch_unwind_async(body: impl IterableTokens, sp: Span) -> TokenStream {
+    // the future is boxed so that polling it through the wrapper needs no pinning
+    let mut future = code("::std::boxed::Box::pin", sp);
+    future.extend([parenthesised([ident("async", sp), braced(body, sp)], sp)]);
+
+    let mut output = code("spoke_after::CatchUnwind", sp);
+    output.extend([parenthesised(future, sp)]);
+    output.extend(code(".await", sp));
+    output
+}
+
+const SUPPORT_MODULE: &str = r#"
+#[allow(dead_code)]
+mod spoke_after {
+    use ::std::{
+        any::Any,
+        future::Future,
+        panic::{AssertUnwindSafe, catch_unwind},
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    pub struct CatchUnwind<F>(pub Pin<Box<F>>);
+
+    impl<F: Future> Future for CatchUnwind<F> {
+        type Output = Result<F::Output, Box<dyn Any + Send>>;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            let future = self.0.as_mut();
+            match catch_unwind(AssertUnwindSafe(|| future.poll(cx))) {
+                Ok(Poll::Pending) => Poll::Pending,
+                Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+                Err(panic) => Poll::Ready(Err(panic)),
+            }
+        }
+    }
+}
+"#;
@@ -31,6 +31,19 @@ pub(crate) fn parenthesised(toks: impl IterableTokens, sp: Span) -> TokenTree {
 pub(crate) fn bracketed(toks: impl IterableTokens, sp: Span) -> TokenTree {
     group(Delimiter::Bracket, toks, sp)
 }
+/// Groups an expression without parentheses, keeping its precedence as an operand
+/// without changing how the expression reads in a failure message.
+pub(crate) fn invisible(toks: impl IterableTokens, sp: Span) -> TokenTree {
+    group(Delimiter::None, toks, sp)
+}
+
+/// `match (scrutinee)`, the parentheses let the expression hold a struct literal,
+/// which an invisible group doesn't, so the lint against them is allowed.
+pub(crate) fn match_on(scrutinee: impl IterableTokens, sp: Span) -> TokenStream {
+    let mut output = code("#[allow(unused_parens)] match", sp);
+    output.extend([parenthesised(scrutinee, sp)]);
+    output
+}
 
 fn group(delim: Delimiter, toks: impl IterableTokens, sp: Span) -> TokenTree {
     let mut stream = TokenStream::new();
@@ -39,3 +52,42 @@ fn group(delim: Delimiter, toks: impl IterableTokens, sp: Span) -> TokenTree {
     grp.set_span(sp);
     grp
 }
+
+/// Parses a fixed fragment of generated code, giving every token the supplied span
+/// so that errors in the expansion point back at the user's test definition.
+pub(crate) fn code(src: &str, sp: Span) -> TokenStream {
+    src.parse::<TokenStream>()
+        .expect("generated code fragments are valid rust")
+        .into_iter()
+        .map(|token| respan(token, sp))
+        .collect()
+}
+
+fn respan(token: TokenTree, sp: Span) -> TokenTree {
+    match token {
+        TokenTree::Group(grp) => group(
+            grp.delimiter(),
+            grp.stream().into_iter().map(|t| respan(t, sp)),
+            sp,
+        ),
+        mut other => {
+            other.set_span(sp);
+            other
+        }
+    }
+}
+
+/// `::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| { body }))`
+pub(crate) fn catch_unwind(body: impl IterableTokens, sp: Span) -> TokenStream {
+    let mut output = code("::std::panic::catch_unwind", sp);
+    output.extend([parenthesised(
+        code("::std::panic::AssertUnwindSafe", sp)
+            .into_iter()
+            .chain([parenthesised(
+                code("||", sp).into_iter().chain([braced(body, sp)]),
+                sp,
+            )]),
+        sp,
+    )]);
+    output
+}
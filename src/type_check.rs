@@ -0,0 +1,23 @@
+use crate::token_helpers::*;
+
+/// The helper that `$type` assertions call, generated once into any suite that uses
+/// it. The expression's type is inferred from the reference rather than coerced to
+/// the expected type, so a `&String` won't pass as a `&str`.
+pub(crate) fn support_module(sp: Span) -> TokenStream {
+    code(SUPPORT_MODULE, sp)
+}
+
+const SUPPORT_MODULE: &str = r#"
+#[allow(dead_code)]
+mod spoke_types {
+    #[diagnostic::on_unimplemented(
+        message = "expected a value of type `{Expected}`, but found `{Self}`",
+        label = "this has the type `{Self}`"
+    )]
+    pub trait SameType<Expected: ?Sized> {}
+
+    impl<T: ?Sized> SameType<T> for T {}
+
+    pub fn check<Expected: ?Sized, Actual: ?Sized + SameType<Expected>>(_: &Actual) {}
+}
+"#;
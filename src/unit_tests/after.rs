@@ -0,0 +1,194 @@
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Span;
+
+    #[allow(unused_imports)]
+    use super::*;
+
+    use crate::{teardown::support_module, unit_tests::testing_helpers::*};
+
+    struct SuiteStructure();
+    impl SurroundingString for SuiteStructure {
+        fn surround(input: &str) -> String {
+            format!(
+                "#[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]  mod spoketest {{ #[allow(unused_imports)] use super::*; {} }}",
+                input
+            )
+        }
+    }
+
+    // async tests can't be caught with catch_unwind so they need the helper future
+    struct AsyncSuiteStructure();
+    impl SurroundingString for AsyncSuiteStructure {
+        fn surround(input: &str) -> String {
+            format!(
+                "#[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]  mod spoketest {{ #[allow(unused_imports)] use super::*; {} {} }}",
+                support_module(Span::call_site()),
+                input
+            )
+        }
+    }
+
+    #[test]
+    fn teardown_runs_after_each_of_the_following_tests() {
+        parsing(Input(
+            r##"
+                $"a file" {
+                    let path = create();
+                    $"exists before cleanup" exists(&path);
+                    $after { remove(&path); }
+                    $"is empty" is_empty(&path);
+                }
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn a_file_exists_before_cleanup() {
+                    let path = create();
+                    assert!(exists(&path));
+                }
+                #[test]
+                fn a_file_is_empty() {
+                    let path = create();
+                    let spoke_outcome = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| {
+                        assert!(is_empty(&path));
+                    }));
+                    { remove(&path); }
+                    if let ::std::result::Result::Err(spoke_panic) = spoke_outcome {
+                        ::std::panic::resume_unwind(spoke_panic);
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn nested_teardowns_run_innermost_first() {
+        parsing(Input(
+            r##"
+                $"a server" {
+                    let server = start();
+                    $after { server.stop(); }
+                    $"with a client" {
+                        let client = server.connect();
+                        $after { client.close(); }
+                        server.send("hello");
+                        $"receives" client.received() $eq "hello";
+                    }
+                }
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn a_server_with_a_client_receives() {
+                    let server = start();
+                    let spoke_outcome = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| {
+                        let client = server.connect();
+                        let spoke_outcome = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| {
+                            server.send("hello");
+                            assert_eq!(client.received(), "hello");
+                        }));
+                        { client.close(); }
+                        if let ::std::result::Result::Err(spoke_panic) = spoke_outcome {
+                            ::std::panic::resume_unwind(spoke_panic);
+                        }
+                    }));
+                    { server.stop(); }
+                    if let ::std::result::Result::Err(spoke_panic) = spoke_outcome {
+                        ::std::panic::resume_unwind(spoke_panic);
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn async_tests_catch_the_panic_of_their_future() {
+        parsing(Input(
+            r##"
+                $async {
+                    let socket = bind().await;
+                    $after { socket.shutdown().await; }
+                    $"accepts" socket.accept().await.is_ok();
+                }
+            "##,
+        ))
+        .matches_inside::<AsyncSuiteStructure>(Expected(
+            r##"
+                #[tokio::test]
+                async fn accepts() {
+                    let socket = bind().await;
+                    let spoke_outcome = spoke_after::CatchUnwind(::std::boxed::Box::pin(async {
+                        assert!(socket.accept().await.is_ok());
+                    })).await;
+                    { socket.shutdown().await; }
+                    if let ::std::result::Result::Err(spoke_panic) = spoke_outcome {
+                        ::std::panic::resume_unwind(spoke_panic);
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn after_needs_a_braced_block() {
+        parsing(Input(
+            r##"
+                $"a file" {
+                    $after remove(&path);
+                    $"still parsed" true;
+                }
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("expected a braced block of code following `$after`, but found `remove`");
+                #[test]
+                fn a_file_still_parsed() {
+                    assert!(true);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn after_cannot_be_marked() {
+        parsing(Input(
+            r##"
+                $"a file" {
+                    $ignore $after { remove(&path); }
+                    $"still parsed" true;
+                }
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("`$after` applies to all of the tests that follow it so it can't be marked");
+                #[test]
+                fn a_file_still_parsed() {
+                    assert!(true);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn after_is_not_allowed_at_the_top_level() {
+        parsing(Input(
+            r##"
+                $after { remove(&path); }
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("expected a valid test case name in quotes following the dollars, but found `after` which is only allowed inside the braced body of a test");
+                #[test]
+                fn missing_name() {
+                    remove(&path);
+                }
+            "##,
+        ));
+    }
+}
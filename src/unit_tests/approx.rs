@@ -0,0 +1,204 @@
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Span;
+
+    #[allow(unused_imports)]
+    use super::*;
+
+    use crate::{
+        approx::support_module, name::*, parse, parser::*, unit_tests::testing_helpers::*,
+    };
+
+    struct SuiteStructure();
+    impl SurroundingString for SuiteStructure {
+        fn surround(input: &str) -> String {
+            format!(
+                "#[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]  mod spoketest {{ #[allow(unused_imports)] use super::*; {} {} }}",
+                support_module(Span::call_site()),
+                input
+            )
+        }
+    }
+
+    #[test]
+    fn an_approx_assertion_checks_the_values_at_runtime() {
+        parse_valid(Input(
+            r##"
+                $"test" circle.area() $approx 3.14159 $within 1e-5;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    {
+                        let spoke_mismatch = spoke_approx::check(&(circle.area()), &(3.14159), spoke_approx::Tolerance::absolute(1e-5));
+                        assert!(spoke_mismatch.is_none(), "expected `{}` to approximately equal `{}`\n{}", "circle . area ()", "3.14159", spoke_mismatch.unwrap_or_default());
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_tolerance_can_be_relative_or_in_ulps() {
+        parse_valid(Input(
+            r##"
+                $"relative" area $approx 3.14159 $within relative 1e-6;
+                $"ulps" area $approx PI $within ulps 4;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_relative() {
+                    {
+                        let spoke_mismatch = spoke_approx::check(&(area), &(3.14159), spoke_approx::Tolerance::relative(1e-6));
+                        assert!(spoke_mismatch.is_none(), "expected `{}` to approximately equal `{}`\n{}", "area", "3.14159", spoke_mismatch.unwrap_or_default());
+                    }
+                }
+                #[test]
+                fn inner_ulps() {
+                    {
+                        let spoke_mismatch = spoke_approx::check(&(area), &(PI), spoke_approx::Tolerance::ulps(4));
+                        assert!(spoke_mismatch.is_none(), "expected `{}` to approximately equal `{}`\n{}", "area", "PI", spoke_mismatch.unwrap_or_default());
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_tolerance_named_like_a_kind_is_absolute() {
+        parse_valid(Input(
+            r##"
+                $"test" area $approx 3.14159 $within relative;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    {
+                        let spoke_mismatch = spoke_approx::check(&(area), &(3.14159), spoke_approx::Tolerance::absolute(relative));
+                        assert!(spoke_mismatch.is_none(), "expected `{}` to approximately equal `{}`\n{}", "area", "3.14159", spoke_mismatch.unwrap_or_default());
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn an_approx_assertion_can_have_a_failure_message() {
+        parse_valid(Input(
+            r##"
+                $"test" area $approx 3.14159 $within 1e-5 $onfail "radius {}", r;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    {
+                        let spoke_mismatch = spoke_approx::check(&(area), &(3.14159), spoke_approx::Tolerance::absolute(1e-5));
+                        assert!(spoke_mismatch.is_none(), "expected `{}` to approximately equal `{}`: {}\n{}", "area", "3.14159", format_args!("radius {}", r), spoke_mismatch.unwrap_or_default());
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn an_approx_assertion_needs_a_tolerance() {
+        parse_valid(Input(
+            r##"
+                $"test" area $approx 3.14159;
+            "##,
+        ))
+        .matches(Expected(
+            r##"
+                #[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]
+                mod spoketest {
+                    #[allow(unused_imports)] use super::*;
+                    compile_error!("expected `$within` and a tolerance following the expected value of the approx assertion");
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_within_needs_a_tolerance() {
+        parse_valid(Input(
+            r##"
+                $"test" area $approx 3.14159 $within;
+            "##,
+        ))
+        .matches(Expected(
+            r##"
+                #[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]
+                mod spoketest {
+                    #[allow(unused_imports)] use super::*;
+                    compile_error!("expected a tolerance following `$within`");
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn only_a_failure_message_can_follow_the_tolerance() {
+        parse_valid(Input(
+            r##"
+                $"test" area $approx 3.14159 $within 1e-5 $eq 3.0;
+            "##,
+        ))
+        .matches(Expected(
+            r##"
+                #[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]
+                mod spoketest {
+                    #[allow(unused_imports)] use super::*;
+                    compile_error!("expected `onfail` following the dollars inside an assertion, but found `eq`");
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_within_without_an_approx_is_an_error() {
+        parse_valid(Input(
+            r##"
+                $"test" area $within 1e-5;
+            "##,
+        ))
+        .matches(Expected(
+            r##"
+                #[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]
+                mod spoketest {
+                    #[allow(unused_imports)] use super::*;
+                    compile_error!("`$within` is only allowed following the expected value of an `$approx` assertion");
+                }
+            "##,
+        ));
+    }
+
+    fn parse_valid(input: Input) -> proc_macro2::TokenStream {
+        let mut output = SuiteGenerator::new();
+
+        let tok = Input(format!("{{ {} }}", input.0).as_str())
+            .stream()
+            .into_iter()
+            .next()
+            .expect("there should be valid input");
+
+        match tok {
+            proc_macro2::TokenTree::Group(group) => parse::Body::generate_body_in_suite(
+                parse::Suite(),
+                Name::new(&Span::call_site(), "inner"),
+                group,
+                &mut output,
+            ),
+            _ => panic!("body parsers can only parse groups"),
+        };
+
+        output.generate_output()
+    }
+}
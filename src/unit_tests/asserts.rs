@@ -5,13 +5,25 @@ mod tests {
     #[allow(unused_imports)]
     use super::*;
 
-    use crate::{name::*, parse, parser::*, unit_tests::testing_helpers::*};
+    use crate::{name::*, parse, parser::*, power_assert, unit_tests::testing_helpers::*};
 
     struct SuiteStructure();
     impl SurroundingString for SuiteStructure {
         fn surround(input: &str) -> String {
             format!(
-                "#[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]  mod spoketest {{ {} }}",
+                "#[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]  mod spoketest {{ #[allow(unused_imports)] use super::*; {} }}",
+                input
+            )
+        }
+    }
+
+    // a plain assertion with a comparison is taken apart by the support module
+    struct PowerSuiteStructure();
+    impl SurroundingString for PowerSuiteStructure {
+        fn surround(input: &str) -> String {
+            format!(
+                "#[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]  mod spoketest {{ #[allow(unused_imports)] use super::*; {} {} }}",
+                power_assert::support_module(Span::call_site()),
                 input
             )
         }
@@ -52,6 +64,563 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn an_ordering_assertion_reports_both_operands() {
+        parse_valid(Input(
+            r##"
+                $"test" latency $lt 100;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    match (&(latency), &(100)) {
+                        (spoke_left, spoke_right) => {
+                            assert!(*spoke_left < *spoke_right, "assertion `left < right` failed\n  left: {:?}\n right: {:?}", spoke_left, spoke_right);
+                        }
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn every_ordering_has_an_assertion() {
+        parse_valid(Input(
+            r##"
+                $"le" a $le b;
+                $"gt" a $gt b;
+                $"ge" a $ge b;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_le() {
+                    match (&(a), &(b)) {
+                        (spoke_left, spoke_right) => {
+                            assert!(*spoke_left <= *spoke_right, "assertion `left <= right` failed\n  left: {:?}\n right: {:?}", spoke_left, spoke_right);
+                        }
+                    }
+                }
+                #[test]
+                fn inner_gt() {
+                    match (&(a), &(b)) {
+                        (spoke_left, spoke_right) => {
+                            assert!(*spoke_left > *spoke_right, "assertion `left > right` failed\n  left: {:?}\n right: {:?}", spoke_left, spoke_right);
+                        }
+                    }
+                }
+                #[test]
+                fn inner_ge() {
+                    match (&(a), &(b)) {
+                        (spoke_left, spoke_right) => {
+                            assert!(*spoke_left >= *spoke_right, "assertion `left >= right` failed\n  left: {:?}\n right: {:?}", spoke_left, spoke_right);
+                        }
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn an_ordering_assertion_can_have_a_failure_message() {
+        parse_valid(Input(
+            r##"
+                $"test" latency $le budget $onfail "over budget by {}", latency - budget;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    match (&(latency), &(budget)) {
+                        (spoke_left, spoke_right) => {
+                            assert!(
+                                *spoke_left <= *spoke_right,
+                                "assertion `left <= right` failed: {}\n  left: {:?}\n right: {:?}",
+                                format_args!("over budget by {}", latency - budget),
+                                spoke_left,
+                                spoke_right
+                            );
+                        }
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn half_an_ordering_assertion_is_an_error() {
+        parse_valid(Input(
+            r##"
+                $"test" latency $gt;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("no code found for the right hand side of the ordering assertion");
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_panics_assertion_catches_only_the_asserted_expression() {
+        parse_valid(Input(
+            r##"
+                $"test" div(1, 0) $panics;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    let spoke_panic = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| { let _ = div(1, 0); }));
+                    assert!(spoke_panic.is_err(), "expected `{}` to panic", "div (1 , 0)");
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_panics_assertion_can_match_the_panic_message() {
+        parse_valid(Input(
+            r##"
+                $"test" div(1, 0) $panics "attempt to divide";
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    let spoke_panic = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| { let _ = div(1, 0); }));
+                    assert!(spoke_panic.is_err(), "expected `{}` to panic", "div (1 , 0)");
+                    let spoke_payload = spoke_panic.unwrap_err();
+                    let spoke_panic_message = spoke_payload
+                        .downcast_ref::<&str>()
+                        .map(|m| m.to_string())
+                        .or_else(|| spoke_payload.downcast_ref::<String>().cloned())
+                        .unwrap_or_default();
+                    assert!(
+                        spoke_panic_message.contains("attempt to divide"),
+                        "expected `{}` to panic with a message containing {:?}, but the message was {:?}",
+                        "div (1 , 0)",
+                        "attempt to divide",
+                        spoke_panic_message
+                    );
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_panics_assertion_without_an_expression_is_an_error() {
+        parse_valid(Input(
+            r##"
+                $"test" $panics;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("no code found for the expression expected to panic");
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_panics_assertion_only_accepts_a_quoted_message() {
+        parse_valid(Input(
+            r##"
+                $"test" div(1, 0) $panics divide;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("expected either `;` or a panic message in quotes following the panics assertion, but found `divide`");
+            "##,
+        ));
+    }
+
+    #[test]
+    fn an_ok_assertion_reports_the_error() {
+        parse_valid(Input(
+            r##"
+                $"test" parse(s) $ok;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    #[allow(unused_parens)] match (parse(s)) {
+                        Ok(_) => {}
+                        Err(spoke_error) => { panic!("expected `{}` to be Ok, but it was Err({:?})", "parse (s)", spoke_error); }
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn an_ok_assertion_can_compare_the_value() {
+        parse_valid(Input(
+            r##"
+                $"test" parse(s) $ok 42;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    #[allow(unused_parens)] match (parse(s)) {
+                        Ok(spoke_value) => { assert_eq!(spoke_value, 42); }
+                        Err(spoke_error) => { panic!("expected `{}` to be Ok, but it was Err({:?})", "parse (s)", spoke_error); }
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn an_err_assertion_reports_the_value() {
+        parse_valid(Input(
+            r##"
+                $"test" parse(bad) $err;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    #[allow(unused_parens)] match (parse(bad)) {
+                        Err(_) => {}
+                        Ok(spoke_value) => { panic!("expected `{}` to be Err, but it was Ok({:?})", "parse (bad)", spoke_value); }
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn option_assertions_match_the_variant() {
+        parse_valid(Input(
+            r##"
+                $"some" name $some "x";
+                $"none" name $none;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_some() {
+                    #[allow(unused_parens)] match (name) {
+                        Some(spoke_value) => { assert_eq!(spoke_value, "x"); }
+                        None => { panic!("expected `{}` to be Some, but it was None", "name"); }
+                    }
+                }
+                #[test]
+                fn inner_none() {
+                    #[allow(unused_parens)] match (name) {
+                        None => {}
+                        Some(spoke_value) => { panic!("expected `{}` to be None, but it was Some({:?})", "name", spoke_value); }
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_variant_assertion_can_start_with_a_struct_literal() {
+        parse_valid(Input(
+            r##"
+                $"x" P { x: 1 } $some;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_x() {
+                    #[allow(unused_parens)] match (P { x: 1 }) {
+                        Some(_) => {}
+                        None => { panic!("expected `{}` to be Some, but it was None", "P { x : 1 }"); }
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_variant_assertion_can_have_a_failure_message() {
+        parse_valid(Input(
+            r##"
+                $"test" parse(s) $ok 42 $onfail "parsing {}", s;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    #[allow(unused_parens)] match (parse(s)) {
+                        Ok(spoke_value) => { assert_eq!(spoke_value, 42, "parsing {}", s); }
+                        Err(spoke_error) => { panic!("parsing {}", s); }
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_none_assertion_cannot_compare_a_value() {
+        parse_valid(Input(
+            r##"
+                $"test" name $none 3;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("expected `;` following the None assertion, but found `3`");
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_variant_assertion_without_an_expression_is_an_error() {
+        parse_valid(Input(
+            r##"
+                $"test" $some;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("no code found for the expression expected to be Some");
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_matches_assertion_reports_the_value() {
+        parse_valid(Input(
+            r##"
+                $"test" result $matches Err(Error::Timeout { .. });
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    #[allow(unused_parens)] match (result) {
+                        ref spoke_value => {
+                            assert!(matches!(spoke_value, Err(Error::Timeout { .. })), "expected `{}` to match `{}`\n value: {:?}", "result", "Err (Error :: Timeout { .. })", spoke_value);
+                        }
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_matches_assertion_can_have_a_guard() {
+        parse_valid(Input(
+            r##"
+                $"test" result $matches Err(Error::Timeout { after }) if *after > 5;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    #[allow(unused_parens)] match (result) {
+                        ref spoke_value => {
+                            assert!(matches!(spoke_value, Err(Error::Timeout { after }) if *after > 5), "expected `{}` to match `{}`\n value: {:?}", "result", "Err (Error :: Timeout { after }) if * after > 5", spoke_value);
+                        }
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_matches_assertion_binds_by_reference_for_the_guard() {
+        parse_valid(Input(
+            r##"
+                $"test" result $matches Err(e) if e.contains("x");
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    #[allow(unused_parens)] match (result) {
+                        ref spoke_value => {
+                            assert!(matches!(spoke_value, Err(e) if e.contains("x")), "expected `{}` to match `{}`\n value: {:?}", "result", "Err (e) if e . contains (\"x\")", spoke_value);
+                        }
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_matches_assertion_can_have_a_failure_message() {
+        parse_valid(Input(
+            r##"
+                $"test" state $matches State::Idle $onfail "after {} steps", n;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    #[allow(unused_parens)] match (state) {
+                        ref spoke_value => {
+                            assert!(matches!(spoke_value, State::Idle), "expected `{}` to match `{}`: {}\n value: {:?}", "state", "State :: Idle", format_args!("after {} steps", n), spoke_value);
+                        }
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_matches_assertion_needs_a_pattern() {
+        parse_valid(Input(
+            r##"
+                $"test" state $matches;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("no pattern found following `$matches`");
+            "##,
+        ));
+    }
+
+    #[test]
+    fn an_assertion_can_have_a_failure_message() {
+        parse_valid(Input(
+            r##"
+                $"test" cart.is_empty() $onfail "the cart still had {} items in it", cart.len();
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    assert!(cart.is_empty(), "the cart still had {} items in it", cart.len());
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn an_equality_assertion_can_have_a_failure_message() {
+        parse_valid(Input(
+            r##"
+                $"test" a $eq b $onfail "a was {}", a;
+                $"test2" a $ne b $onfail "same";
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    assert_eq!(a, b, "a was {}", a);
+                }
+                #[test]
+                fn inner_test2() {
+                    assert_ne!(a, b, "same");
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_panics_assertion_can_have_a_failure_message() {
+        parse_valid(Input(
+            r##"
+                $"test" div(1, 0) $panics $onfail "dividing by {} should fail", 0;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    let spoke_panic = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| { let _ = div(1, 0); }));
+                    assert!(spoke_panic.is_err(), "dividing by {} should fail", 0);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_failure_message_cannot_be_empty() {
+        parse_valid(Input(
+            r##"
+                $"test" true $onfail;
+                $"test2" a $eq b $onfail;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("expected a failure message following onfail, but found `;`");
+                compile_error!("expected a failure message following onfail, but found `;`");
+            "##,
+        ));
+    }
+
+    #[test]
+    fn only_a_failure_message_can_follow_the_assertion() {
+        parse_valid(Input(
+            r##"
+                $"test" a $eq b $ne c;
+                $"test2" a $eq b $;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("expected `onfail` following the dollars inside an assertion, but found `ne`");
+                compile_error!("expected `onfail` following the dollars inside an assertion, but found `;`");
+            "##,
+        ));
+    }
+
+    #[test]
+    fn an_assertion_can_be_named_after_its_code() {
+        parse_valid(Input(
+            r##"
+                $$ basket.is_empty();
+                $$ basket.len() $eq 0;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_basket_dot_is_empty_parens() {
+                    assert!(basket.is_empty());
+                }
+                #[test]
+                fn inner_basket_dot_len_parens_eq_0() {
+                    assert_eq!(basket.len(), 0);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn an_auto_named_assertion_still_needs_code() {
+        parse_valid(Input(
+            r##"
+                $$;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("expected an assertion or test body after the name, but found `;`");
+            "##,
+        ));
+    }
+
     #[test]
     fn half_an_assertion_is_an_error_left() {
         parse_valid(Input(
@@ -145,10 +714,21 @@ mod tests {
                 $"test" 7 < {let x = 5; x *2 } ;
             "##,
         ))
-        .matches_inside::<SuiteStructure>(Expected(
+        .matches_inside::<PowerSuiteStructure>(Expected(
             r##"#[test] 
                 fn inner_test() {
-                    assert!(7 < {let x = 5; x *2});
+                    {
+                        #[allow(unused_imports)]
+                        use spoke_power::{ViaDebug as _, WithoutDebug as _};
+                        let mut spoke_values = spoke_power::Values::new();
+                        let spoke_passed = match (&(7), &({let x = 5; x *2})) {
+                            (spoke_left, spoke_right) => {
+                                spoke_values.record("7 < { let x = 5 ; x * 2 }", format!("{} < {}", (&spoke_power::Value(spoke_left)).spoke_debug(), (&spoke_power::Value(spoke_right)).spoke_debug()));
+                                *spoke_left < *spoke_right
+                            }
+                        };
+                        assert!(spoke_passed, "assertion failed: {}\n{}", "7 < { let x = 5 ; x * 2 }", spoke_values);
+                    }
                 }
             "##,
         ));
@@ -161,10 +741,21 @@ mod tests {
                 $"test" ({let x = 5; x *2 } > 7) ;
             "##,
         ))
-        .matches_inside::<SuiteStructure>(Expected(
+        .matches_inside::<PowerSuiteStructure>(Expected(
             r##"#[test] 
                 fn inner_test() {
-                    assert!(({let x = 5; x *2 } > 7));
+                    {
+                        #[allow(unused_imports)]
+                        use spoke_power::{ViaDebug as _, WithoutDebug as _};
+                        let mut spoke_values = spoke_power::Values::new();
+                        let spoke_passed = match (&({let x = 5; x *2 }), &(7)) {
+                            (spoke_left, spoke_right) => {
+                                spoke_values.record("{ let x = 5 ; x * 2 } > 7", format!("{} > {}", (&spoke_power::Value(spoke_left)).spoke_debug(), (&spoke_power::Value(spoke_right)).spoke_debug()));
+                                *spoke_left > *spoke_right
+                            }
+                        };
+                        assert!(spoke_passed, "assertion failed: {}\n{}", "({ let x = 5 ; x * 2 } > 7)", spoke_values);
+                    }
                 }
             "##,
         ));
@@ -179,7 +770,7 @@ mod tests {
         ))
         .matches_inside::<SuiteStructure>(Expected(
             r##"
-                compile_error!("expected a valid assertion type [eq,ne] following the dollars, but found `plop`");
+                compile_error!("expected a valid assertion type [eq,ne,lt,le,gt,ge,ok,err,some,none,matches,approx,within,contains,len,unordered_eq,impl,type,panics,onfail] following the dollars, but found `plop`");
             "##,
         ));
     }
@@ -193,7 +784,7 @@ mod tests {
         ))
         .matches_inside::<SuiteStructure>(Expected(
             r##"
-                compile_error!("expected a valid assertion type [eq,ne] following the dollars, but found an incorrectly cased match `EQ` - asserts are all lowercase");
+                compile_error!("expected a valid assertion type [eq,ne,lt,le,gt,ge,ok,err,some,none,matches,approx,within,contains,len,unordered_eq,impl,type,panics,onfail] following the dollars, but found an incorrectly cased match `EQ` - asserts are all lowercase");
                 #[test]
                 fn inner_test () {
                     assert_eq!(true, false); 
@@ -242,7 +833,7 @@ mod tests {
         ))
         .matches_inside::<SuiteStructure>(Expected(
             r##"
-                compile_error!("expected a valid assertion type [eq,ne] following the dollars, but found `\"lit\"` which looks like a test defintion. Tests cannot be nested inside asserts.");
+                compile_error!("expected a valid assertion type [eq,ne,lt,le,gt,ge,ok,err,some,none,matches,approx,within,contains,len,unordered_eq,impl,type,panics,onfail] following the dollars, but found `\"lit\"` which looks like a test defintion. Tests cannot be nested inside asserts.");
                 compile_error!("expected an assertion or test body after the name, but found `;`"); 
             "##,
         ));
@@ -257,7 +848,7 @@ mod tests {
         ))
         .matches_inside::<SuiteStructure>(Expected(
             r##"
-                compile_error!("expected a valid assertion type [eq,ne] following the dollars, but found `;`");
+                compile_error!("expected a valid assertion type [eq,ne,lt,le,gt,ge,ok,err,some,none,matches,approx,within,contains,len,unordered_eq,impl,type,panics,onfail] following the dollars, but found `;`");
             "##,
         ));
     }
@@ -271,7 +862,7 @@ mod tests {
         ))
         .matches_inside::<SuiteStructure>(Expected(
             r##"
-                compile_error!("expected a valid assertion type [eq,ne] following the dollars, but found `#`");
+                compile_error!("expected a valid assertion type [eq,ne,lt,le,gt,ge,ok,err,some,none,matches,approx,within,contains,len,unordered_eq,impl,type,panics,onfail] following the dollars, but found `#`");
             "##,
         ));
     }
@@ -299,7 +890,7 @@ mod tests {
         ))
         .matches_inside::<SuiteStructure>(Expected(
             r##"
-                compile_error!("expected a valid assertion type [eq,ne] following the dollars, but found `#`");
+                compile_error!("expected a valid assertion type [eq,ne,lt,le,gt,ge,ok,err,some,none,matches,approx,within,contains,len,unordered_eq,impl,type,panics,onfail] following the dollars, but found `#`");
                 compile_error!("reached end of group input before reaching the end of the assertion definition");
             "##,
         ));
@@ -0,0 +1,230 @@
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Span;
+
+    #[allow(unused_imports)]
+    use super::*;
+
+    use crate::{teardown::support_module, unit_tests::testing_helpers::*};
+
+    struct SuiteStructure();
+    impl SurroundingString for SuiteStructure {
+        fn surround(input: &str) -> String {
+            format!(
+                "#[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]  mod spoketest {{ #[allow(unused_imports)] use super::*; {} }}",
+                input
+            )
+        }
+    }
+
+    struct AsyncSuiteStructure();
+    impl SurroundingString for AsyncSuiteStructure {
+        fn surround(input: &str) -> String {
+            format!(
+                "#[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]  mod spoketest {{ #[allow(unused_imports)] use super::*; {} {} }}",
+                support_module(Span::call_site()),
+                input
+            )
+        }
+    }
+
+    #[test]
+    fn tests_inside_an_async_block_are_async() {
+        parsing(Input(
+            r##"
+                $async {
+                    $"fetches the user" fetch_user(1).await.is_ok();
+                }
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[tokio::test]
+                async fn fetches_the_user() {
+                    assert!(fetch_user(1).await.is_ok());
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn an_async_block_can_pass_arguments_to_the_runtime() {
+        parsing(Input(
+            r##"
+                $async(flavor = "multi_thread") {
+                    $"fetches the user" fetch_user(1).await.is_ok();
+                }
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[tokio::test(flavor = "multi_thread")]
+                async fn fetches_the_user() {
+                    assert!(fetch_user(1).await.is_ok());
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn an_async_block_adds_no_name_and_shares_its_code() {
+        parsing(Input(
+            r##"
+                $"the service" {
+                    let service = Service::new();
+                    $async {
+                        let user = service.user(1).await;
+                        $"finds the user" user.is_some();
+                        $"names the user" {
+                            let name = user.unwrap().name().await;
+                            $"as bob" name $eq "bob";
+                        }
+                    }
+                    $"starts stopped" !service.is_running();
+                }
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[tokio::test]
+                async fn the_service_finds_the_user() {
+                    let service = Service::new();
+                    let user = service.user(1).await;
+                    assert!(user.is_some());
+                }
+                #[tokio::test]
+                async fn the_service_names_the_user_as_bob() {
+                    let service = Service::new();
+                    let user = service.user(1).await;
+                    let name = user.unwrap().name().await;
+                    assert_eq!(name, "bob");
+                }
+                #[test]
+                fn the_service_starts_stopped() {
+                    let service = Service::new();
+                    assert!(!service.is_running());
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn the_innermost_async_arguments_are_used() {
+        parsing(Input(
+            r##"
+                $async(flavor = "multi_thread") {
+                    $async(flavor = "current_thread") {
+                        $"runs" true;
+                    }
+                }
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[tokio::test(flavor = "current_thread")]
+                async fn runs() {
+                    assert!(true);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn async_tests_can_be_ignored() {
+        parsing(Input(
+            r##"
+                $ignore $async {
+                    $"runs" true;
+                }
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[tokio::test]
+                #[ignore]
+                async fn runs() {
+                    assert!(true);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn async_tests_can_be_pending() {
+        parsing(Input(
+            r##"
+                $async {
+                    $pending "finds the user" fetch_user(1).await.is_ok();
+                }
+            "##,
+        ))
+        .matches_inside::<AsyncSuiteStructure>(Expected(
+            r##"
+                #[tokio::test]
+                async fn finds_the_user() {
+                    let spoke_pending = spoke_after::CatchUnwind(::std::boxed::Box::pin(async {
+                        assert!(fetch_user(1).await.is_ok());
+                    })).await;
+                    assert!(spoke_pending.is_err(), "this pending test now passes, remove $pending");
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn async_panics_assertions_await_the_expression() {
+        parsing(Input(
+            r##"
+                $async {
+                    $"rejects the id" fetch_user(0).await $panics;
+                }
+            "##,
+        ))
+        .matches_inside::<AsyncSuiteStructure>(Expected(
+            r##"
+                #[tokio::test]
+                async fn rejects_the_id() {
+                    let spoke_panic = spoke_after::CatchUnwind(::std::boxed::Box::pin(async {
+                        let _ = fetch_user(0).await;
+                    })).await;
+                    assert!(spoke_panic.is_err(), "expected `{}` to panic", "fetch_user (0) . await");
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn an_async_block_needs_tests() {
+        parsing(Input(
+            r##"
+                $async {
+                    let x = 5;
+                }
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("expected at least one test inside the `$async` block");
+            "##,
+        ));
+    }
+
+    #[test]
+    fn async_must_be_followed_by_a_block() {
+        parsing(Input(
+            r##"
+                $async "runs" true;
+                $"next" true;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("expected a braced block of tests following `$async`, but found `\"runs\"`");
+                #[test]
+                fn next() {
+                    assert!(true);
+                }
+            "##,
+        ));
+    }
+}
@@ -0,0 +1,330 @@
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Span;
+
+    #[allow(unused_imports)]
+    use super::*;
+
+    use crate::{
+        backend::{Backend, Comparison},
+        name::*,
+        parse,
+        parser::*,
+        token_helpers::code,
+        unit_tests::testing_helpers::*,
+    };
+
+    struct SuiteStructure();
+    impl SurroundingString for SuiteStructure {
+        fn surround(input: &str) -> String {
+            format!(
+                "#[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]  mod spoketest {{ #[allow(unused_imports)] use super::*; {} }}",
+                input
+            )
+        }
+    }
+
+    #[test]
+    fn assert2_checks_plain_assertions() {
+        parse_with_assert2(Input(
+            r##"
+                $"test" v.is_empty();
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    ::assert2::assert!(v.is_empty());
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn assert2_takes_apart_plain_comparisons_itself() {
+        parse_with_assert2(Input(
+            r##"
+                $"test" user.age() >= limit && user.is_active();
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    ::assert2::assert!(user.age() >= limit && user.is_active());
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn assert2_checks_equality_as_a_comparison() {
+        parse_with_assert2(Input(
+            r##"
+                $"test" v.len() $eq 1;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    ::assert2::assert!(v.len() == 1);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn assert2_checks_inequality_as_a_comparison() {
+        parse_with_assert2(Input(
+            r##"
+                $"test" v.len() $ne 1;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    ::assert2::assert!(v.len() != 1);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn assert2_checks_orderings_as_a_comparison() {
+        parse_with_assert2(Input(
+            r##"
+                $"test" latency $ge 100;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    ::assert2::assert!(latency >= 100);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn assert2_keeps_custom_failure_messages() {
+        parse_with_assert2(Input(
+            r##"
+                $"test" v.len() $eq 1 $onfail "length was {}", v.len();
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    ::assert2::assert!(v.len() == 1, "length was {}", v.len());
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn assert2_checks_panics() {
+        parse_with_assert2(Input(
+            r##"
+                $"test" v[3] $panics;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    let spoke_panic = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| {
+                        let _ = v[3];
+                    }));
+                    ::assert2::assert!(spoke_panic.is_err(), "expected `{}` to panic", "v [3]");
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn assert2_compares_the_value_inside_a_variant() {
+        parse_with_assert2(Input(
+            r##"
+                $"test" parse(s) $ok 42;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    #[allow(unused_parens)] match (parse(s)) {
+                        Ok(spoke_value) => { ::assert2::assert!(spoke_value == 42); }
+                        Err(spoke_error) => { panic!("expected `{}` to be Ok, but it was Err({:?})", "parse (s)", spoke_error); }
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn assert2_checks_patterns() {
+        parse_with_assert2(Input(
+            r##"
+                $"test" state $matches State::Idle;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    #[allow(unused_parens)] match (state) {
+                        ref spoke_value => {
+                            ::assert2::assert!(matches!(spoke_value, State::Idle), "expected `{}` to match `{}`\n value: {:?}", "state", "State :: Idle", spoke_value);
+                        }
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn diff_checks_equality_with_the_support_crate() {
+        parse_with_diff(Input(
+            r##"
+                $"test" tree $eq expected;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    ::spoke_support::assert_eq(&(tree), &(expected), ::std::option::Option::None);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn diff_passes_on_custom_failure_messages() {
+        parse_with_diff(Input(
+            r##"
+                $"test" tree $eq expected $onfail "parsing {}", source;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    ::spoke_support::assert_eq(&(tree), &(expected), ::std::option::Option::Some(format_args!("parsing {}", source)));
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn diff_leaves_the_other_assertions_to_the_standard_library() {
+        parse_with_diff(Input(
+            r##"
+                $"plain" v.is_empty();
+                $"ne" v.len() $ne 1;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_plain() {
+                    assert!(v.is_empty());
+                }
+                #[test]
+                fn inner_ne() {
+                    assert_ne!(v.len(), 1);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn the_diff_feature_selects_the_diff_backend() {
+        parse_with_backend(
+            Backend::from_features(false, true),
+            Input(
+                r##"
+                $"test" tree $eq expected;
+            "##,
+            ),
+        )
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    ::spoke_support::assert_eq(&(tree), &(expected), ::std::option::Option::None);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn assert2_comparisons_keep_the_precedence_of_each_side() {
+        let assertion = Backend::Assert2.compare(
+            code("a || b", Span::call_site()),
+            Comparison::Eq,
+            code("c", Span::call_site()),
+            [],
+            Span::call_site(),
+        );
+
+        let arguments = assertion
+            .iter()
+            .find_map(|token| match token {
+                TokenTree::Group(arguments) if arguments.delimiter() == Delimiter::Parenthesis => {
+                    Some(arguments)
+                }
+                _ => None,
+            })
+            .expect("the assertion has arguments");
+
+        let sides = arguments
+            .stream()
+            .into_iter()
+            .filter_map(|token| match token {
+                TokenTree::Group(side) if side.delimiter() == Delimiter::None => {
+                    Some(side.stream().to_string())
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(sides, ["a || b", "c"]);
+    }
+
+    fn parse_with_assert2(input: Input) -> proc_macro2::TokenStream {
+        parse_with_backend(Backend::Assert2, input)
+    }
+
+    fn parse_with_diff(input: Input) -> proc_macro2::TokenStream {
+        parse_with_backend(Backend::Diff, input)
+    }
+
+    fn parse_with_backend(backend: Backend, input: Input) -> proc_macro2::TokenStream {
+        let mut output = SuiteGenerator::new();
+        output.set_backend(backend);
+
+        let tok = Input(format!("{{ {} }}", input.0).as_str())
+            .stream()
+            .into_iter()
+            .next()
+            .expect("there should be valid input");
+
+        match tok {
+            proc_macro2::TokenTree::Group(group) => parse::Body::generate_body_in_suite(
+                parse::Suite(),
+                Name::new(&Span::call_site(), "inner"),
+                group,
+                &mut output,
+            ),
+            _ => panic!("body parsers can only parse groups"),
+        };
+
+        output.generate_output()
+    }
+}
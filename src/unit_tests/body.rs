@@ -11,7 +11,7 @@ mod tests {
     impl SurroundingString for SuiteStructure {
         fn surround(input: &str) -> String {
             format!(
-                "#[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]  mod spoketest {{ {} }}",
+                "#[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]  mod spoketest {{ #[allow(unused_imports)] use super::*; {} }}",
                 input
             )
         }
@@ -203,6 +203,81 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn code_after_a_child_only_applies_to_later_siblings() {
+        parse_valid(Input(
+            r##"
+                let mut v = Vec::new();
+                $"starts empty" v.is_empty();
+                v.push(1);
+                $"after a push" {
+                    $"has one item" v.len() $eq 1;
+                    v.push(2);
+                    $"then has two" v.len() $eq 2;
+                }
+                v.clear();
+                $"is empty again" v.is_empty();
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn a_test_starts_empty() {
+                    let mut v = Vec::new();
+                    assert!(v.is_empty());
+                }
+                #[test]
+                fn a_test_after_a_push_has_one_item() {
+                    let mut v = Vec::new();
+                    v.push(1);
+                    assert_eq!(v.len(), 1);
+                }
+                #[test]
+                fn a_test_after_a_push_then_has_two() {
+                    let mut v = Vec::new();
+                    v.push(1);
+                    v.push(2);
+                    assert_eq!(v.len(), 2);
+                }
+                #[test]
+                fn a_test_is_empty_again() {
+                    let mut v = Vec::new();
+                    v.push(1);
+                    v.clear();
+                    assert!(v.is_empty());
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn code_after_a_marked_child_only_applies_to_later_siblings() {
+        parse_valid(Input(
+            r##"
+                let mut v = Vec::new();
+                $ignore $"skipped" v.is_empty();
+                v.push(1);
+                $"has one item" v.len() $eq 1;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                #[ignore]
+                fn a_test_skipped() {
+                    let mut v = Vec::new();
+                    assert!(v.is_empty());
+                }
+                #[test]
+                fn a_test_has_one_item() {
+                    let mut v = Vec::new();
+                    v.push(1);
+                    assert_eq!(v.len(), 1);
+                }
+            "##,
+        ));
+    }
+
     #[test]
     fn running_out_of_input_in_a_test_is_an_error() {
         parse_valid(Input(
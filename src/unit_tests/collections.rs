@@ -0,0 +1,183 @@
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Span;
+
+    #[allow(unused_imports)]
+    use super::*;
+
+    use crate::{
+        collections::support_module, name::*, parse, parser::*, unit_tests::testing_helpers::*,
+    };
+
+    struct SuiteStructure();
+    impl SurroundingString for SuiteStructure {
+        fn surround(input: &str) -> String {
+            format!(
+                "#[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]  mod spoketest {{ #[allow(unused_imports)] use super::*; {} {} }}",
+                support_module(Span::call_site()),
+                input
+            )
+        }
+    }
+
+    // a length is checked without any of the support module
+    struct PlainSuiteStructure();
+    impl SurroundingString for PlainSuiteStructure {
+        fn surround(input: &str) -> String {
+            format!(
+                "#[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]  mod spoketest {{ #[allow(unused_imports)] use super::*; {} }}",
+                input
+            )
+        }
+    }
+
+    #[test]
+    fn a_contains_assertion_searches_the_collection_at_runtime() {
+        parse_valid(Input(
+            r##"
+                $"test" tags $contains "admin";
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    {
+                        #[allow(unused_imports)]
+                        use spoke_collections::{ContainsItem as _, ContainsText as _};
+                        let spoke_mismatch = (&&spoke_collections::Haystack::new(tags)).spoke_contains("admin");
+                        assert!(spoke_mismatch.is_none(), "expected `{}` to contain `{}`\n{}", "tags", "\"admin\"", spoke_mismatch.unwrap_or_default());
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn an_unordered_assertion_compares_the_collections_at_runtime() {
+        parse_valid(Input(
+            r##"
+                $"test" ids $unordered_eq [3, 1, 2];
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    {
+                        let spoke_mismatch = spoke_collections::unordered_eq(ids, [3, 1, 2]);
+                        assert!(spoke_mismatch.is_none(), "expected `{}` to equal `{}` in any order\n{}", "ids", "[3 , 1 , 2]", spoke_mismatch.unwrap_or_default());
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn the_support_module_is_only_generated_once() {
+        parse_valid(Input(
+            r##"
+                $"first" ids $contains 1;
+                $"second" ids $unordered_eq [1];
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_first() {
+                    {
+                        #[allow(unused_imports)]
+                        use spoke_collections::{ContainsItem as _, ContainsText as _};
+                        let spoke_mismatch = (&&spoke_collections::Haystack::new(ids)).spoke_contains(1);
+                        assert!(spoke_mismatch.is_none(), "expected `{}` to contain `{}`\n{}", "ids", "1", spoke_mismatch.unwrap_or_default());
+                    }
+                }
+                #[test]
+                fn inner_second() {
+                    {
+                        let spoke_mismatch = spoke_collections::unordered_eq(ids, [1]);
+                        assert!(spoke_mismatch.is_none(), "expected `{}` to equal `{}` in any order\n{}", "ids", "[1]", spoke_mismatch.unwrap_or_default());
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_len_assertion_reports_the_collection() {
+        parse_valid(Input(
+            r##"
+                $"test" basket $len 3;
+            "##,
+        ))
+        .matches_inside::<PlainSuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    match (&(basket), 3) {
+                        (spoke_collection, spoke_expected) => {
+                            assert!(spoke_collection.len() == spoke_expected, "expected `{}` to have a length of {}, but it was {}\ncollection: {:?}", "basket", spoke_expected, spoke_collection.len(), spoke_collection);
+                        }
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_collection_assertion_can_have_a_failure_message() {
+        parse_valid(Input(
+            r##"
+                $"test" basket $len 3 $onfail "after adding {}", item;
+            "##,
+        ))
+        .matches_inside::<PlainSuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    match (&(basket), 3) {
+                        (spoke_collection, spoke_expected) => {
+                            assert!(spoke_collection.len() == spoke_expected, "expected `{}` to have a length of {}, but it was {}: {}\ncollection: {:?}", "basket", spoke_expected, spoke_collection.len(), format_args!("after adding {}", item), spoke_collection);
+                        }
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn half_a_collection_assertion_is_an_error() {
+        parse_valid(Input(
+            r##"
+                $"test" tags $contains;
+            "##,
+        ))
+        .matches_inside::<PlainSuiteStructure>(Expected(
+            r##"
+                compile_error!("no code found for the right hand side of the contains assertion");
+            "##,
+        ));
+    }
+
+    fn parse_valid(input: Input) -> proc_macro2::TokenStream {
+        let mut output = SuiteGenerator::new();
+
+        let tok = Input(format!("{{ {} }}", input.0).as_str())
+            .stream()
+            .into_iter()
+            .next()
+            .expect("there should be valid input");
+
+        match tok {
+            proc_macro2::TokenTree::Group(group) => parse::Body::generate_body_in_suite(
+                parse::Suite(),
+                Name::new(&Span::call_site(), "inner"),
+                group,
+                &mut output,
+            ),
+            _ => panic!("body parsers can only parse groups"),
+        };
+
+        output.generate_output()
+    }
+}
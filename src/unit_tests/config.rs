@@ -0,0 +1,441 @@
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    use crate::{spoke, unit_tests::testing_helpers::*};
+
+    #[test]
+    fn config_changes_the_module_name() {
+        parsing(Input(
+            r##"
+            $config(module = vector_tests);
+            $"first test" {}
+            "##,
+        ))
+        .matches(Expected(
+            r##"
+            #[cfg(test)]
+            #[allow(unused_mut)]
+            #[allow(unused_variables)]
+            mod vector_tests {
+                #[allow(unused_imports)]
+                use super::*;
+                #[test] fn first_test() { }
+            }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn config_can_turn_off_the_super_import() {
+        parsing(Input(
+            r##"
+            $config(super = off);
+            $"first test" {}
+            "##,
+        ))
+        .matches(Expected(
+            r##"
+            #[cfg(test)]
+            #[allow(unused_mut)]
+            #[allow(unused_variables)]
+            mod spoketest_first_test_4e6979ca {
+                #[test] fn first_test() { }
+            }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn config_accepts_multiple_options() {
+        parsing(Input(
+            r##"
+            use std::collections::HashMap;
+            $config(module = maps, super = on,);
+            $"first test" {}
+            "##,
+        ))
+        .matches(Expected(
+            r##"
+            #[cfg(test)]
+            #[allow(unused_mut)]
+            #[allow(unused_variables)]
+            mod maps {
+                #[allow(unused_imports)]
+                use super::*;
+                use std::collections::HashMap;
+                #[test] fn first_test() { }
+            }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn config_after_a_test_is_an_error() {
+        parsing(Input(
+            r##"
+            $"first test" {}
+            $config(super = off);
+            "##,
+        ))
+        .matches(Expected(
+            r##"
+            #[cfg(test)]
+            #[allow(unused_mut)]
+            #[allow(unused_variables)]
+            mod spoketest_first_test_de9c12c8 {
+                #[allow(unused_imports)]
+                use super::*;
+                compile_error!("`$config` is only allowed in the suite preamble, before any tests are defined");
+                #[test] fn first_test() { }
+            }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn config_inside_a_body_is_an_error() {
+        parsing(Input(
+            r##"
+            $"first test" {
+                $config(super = off);
+            }
+            "##,
+        ))
+        .matches(Expected(
+            r##"
+            #[cfg(test)]
+            #[allow(unused_mut)]
+            #[allow(unused_variables)]
+            mod spoketest_first_test_3c5b6ea2 {
+                #[allow(unused_imports)]
+                use super::*;
+                compile_error!("expected a valid test name following the dollars, but found `config` which is only allowed in the suite preamble");
+            }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn unknown_config_options_are_listed() {
+        parsing(Input(
+            r##"
+            $config(color = blue);
+            "##,
+        ))
+        .matches(Expected(
+            r##"
+            #[cfg(test)]
+            #[allow(unused_mut)]
+            #[allow(unused_variables)]
+            mod spoketest {
+                #[allow(unused_imports)]
+                use super::*;
+                compile_error!("expected one of the config options [module,super,async], but found `color`");
+            }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn config_options_can_only_be_set_once() {
+        parsing(Input(
+            r##"
+            $config(module = first, module = second);
+            "##,
+        ))
+        .matches(Expected(
+            r##"
+            #[cfg(test)]
+            #[allow(unused_mut)]
+            #[allow(unused_variables)]
+            mod first {
+                #[allow(unused_imports)]
+                use super::*;
+                compile_error!("the `module` option has already been configured");
+            }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn super_must_be_on_or_off() {
+        parsing(Input(
+            r##"
+            $config(super = yes);
+            "##,
+        ))
+        .matches(Expected(
+            r##"
+            #[cfg(test)]
+            #[allow(unused_mut)]
+            #[allow(unused_variables)]
+            mod spoketest {
+                #[allow(unused_imports)]
+                use super::*;
+                compile_error!("expected either `on` or `off` for the `super` option, but found `yes`");
+            }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn module_must_be_a_single_name() {
+        parsing(Input(
+            r##"
+            $config(module = "tests");
+            "##,
+        ))
+        .matches(Expected(
+            r##"
+            #[cfg(test)]
+            #[allow(unused_mut)]
+            #[allow(unused_variables)]
+            mod spoketest {
+                #[allow(unused_imports)]
+                use super::*;
+                compile_error!("expected a module name for the `module` option, but found `\"tests\"`");
+            }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn config_requires_a_semicolon() {
+        parsing(Input(
+            r##"
+            $config(super = off) $"first test" {}
+            "##,
+        ))
+        .matches(Expected(
+            r##"
+            #[cfg(test)]
+            #[allow(unused_mut)]
+            #[allow(unused_variables)]
+            mod spoketest {
+                compile_error!("expected `;` following the config options, but found `$`");
+                compile_error!("expected `;` following the config options, but found `\"first test\"`");
+                compile_error!("expected `;` following the config options, but found `{ }`");
+                compile_error!("reached end of input before reaching the end of the config definition");
+            }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn config_can_change_the_async_test_attribute() {
+        parsing(Input(
+            r##"
+            $config(module = runtime, async = derive = my_rt::test);
+            $async {
+                $"runs" ready().await;
+            }
+            "##,
+        ))
+        .matches(Expected(
+            r##"
+            #[cfg(test)]
+            #[allow(unused_mut)]
+            #[allow(unused_variables)]
+            mod runtime {
+                #[allow(unused_imports)]
+                use super::*;
+                #[my_rt::test] async fn runs() { assert!(ready().await); }
+            }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn async_block_arguments_replace_those_of_the_configured_attribute() {
+        parsing(Input(
+            r##"
+            $config(module = runtime, async = derive = tokio::test(flavor = "multi_thread"));
+            $async {
+                $"runs" ready().await;
+            }
+            $async(flavor = "current_thread") {
+                $"runs locally" ready().await;
+            }
+            "##,
+        ))
+        .matches(Expected(
+            r##"
+            #[cfg(test)]
+            #[allow(unused_mut)]
+            #[allow(unused_variables)]
+            mod runtime {
+                #[allow(unused_imports)]
+                use super::*;
+                #[tokio::test(flavor = "multi_thread")] async fn runs() { assert!(ready().await); }
+                #[tokio::test(flavor = "current_thread")] async fn runs_locally() { assert!(ready().await); }
+            }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn config_can_run_async_tests_with_a_function() {
+        parsing(Input(
+            r##"
+            $config(module = runtime, async = fn = smol::block_on);
+            $async {
+                let value = 5;
+                $"runs" ready().await;
+            }
+            "##,
+        ))
+        .matches(Expected(
+            r##"
+            #[cfg(test)]
+            #[allow(unused_mut)]
+            #[allow(unused_variables)]
+            mod runtime {
+                #[allow(unused_imports)]
+                use super::*;
+                #[test] fn runs() {
+                    smol::block_on(async {
+                        let value = 5;
+                        assert!(ready().await);
+                    });
+                }
+            }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_runtime_function_can_be_an_expression() {
+        parsing(Input(
+            r##"
+            $config(module = runtime, async = fn = { Runtime::new().unwrap() }.block_on);
+            $async {
+                $"runs" ready().await;
+            }
+            "##,
+        ))
+        .matches(Expected(
+            r##"
+            #[cfg(test)]
+            #[allow(unused_mut)]
+            #[allow(unused_variables)]
+            mod runtime {
+                #[allow(unused_imports)]
+                use super::*;
+                #[test] fn runs() {
+                    { Runtime::new().unwrap() }.block_on(async {
+                        assert!(ready().await);
+                    });
+                }
+            }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_runtime_function_cannot_take_async_block_arguments() {
+        parsing(Input(
+            r##"
+            $config(module = runtime, async = fn = smol::block_on);
+            $async(flavor = "multi_thread") {
+                $"runs" ready().await;
+            }
+            "##,
+        ))
+        .matches(Expected(
+            r##"
+            #[cfg(test)]
+            #[allow(unused_mut)]
+            #[allow(unused_variables)]
+            mod runtime {
+                #[allow(unused_imports)]
+                use super::*;
+                compile_error!("arguments can't be given to `$async` when the runtime is configured with `async = fn = ...`");
+                #[test] fn runs() {
+                    smol::block_on(async {
+                        assert!(ready().await);
+                    });
+                }
+            }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn async_tests_run_by_a_function_can_be_pending() {
+        parsing(Input(
+            r##"
+            $config(module = runtime, async = fn = smol::block_on);
+            $async {
+                $pending "runs" ready().await;
+            }
+            "##,
+        ))
+        .matches(Expected(
+            r##"
+            #[cfg(test)]
+            #[allow(unused_mut)]
+            #[allow(unused_variables)]
+            mod runtime {
+                #[allow(unused_imports)]
+                use super::*;
+                #[test] fn runs() {
+                    let spoke_pending = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| {
+                        smol::block_on(async {
+                            assert!(ready().await);
+                        });
+                    }));
+                    assert!(spoke_pending.is_err(), "this pending test now passes, remove $pending");
+                }
+            }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn the_async_runtime_must_be_derive_or_fn() {
+        parsing(Input(
+            r##"
+            $config(async = smol::block_on);
+            "##,
+        ))
+        .matches(Expected(
+            r##"
+            #[cfg(test)]
+            #[allow(unused_mut)]
+            #[allow(unused_variables)]
+            mod spoketest {
+                #[allow(unused_imports)]
+                use super::*;
+                compile_error!("expected either `derive = path` or `fn = path` for the `async` option, but found `smol`");
+            }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn the_async_runtime_needs_a_path() {
+        parsing(Input(
+            r##"
+            $config(async = derive =);
+            "##,
+        ))
+        .matches(Expected(
+            r##"
+            #[cfg(test)]
+            #[allow(unused_mut)]
+            #[allow(unused_variables)]
+            mod spoketest {
+                #[allow(unused_imports)]
+                use super::*;
+                compile_error!("expected the path of the async runtime following `derive =`, but found nothing");
+            }
+            "##,
+        ));
+    }
+
+    fn parsing(input: Input) -> proc_macro2::TokenStream {
+        spoke::generate_tests(input.stream())
+    }
+}
@@ -0,0 +1,207 @@
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    use crate::{spoke, unit_tests::testing_helpers::*};
+
+    struct SuiteStructure();
+    impl SurroundingString for SuiteStructure {
+        fn surround(input: &str) -> String {
+            format!(
+                "#[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]  mod spoketest {{ #[allow(unused_imports)] use super::*; {} }}",
+                input
+            )
+        }
+    }
+
+    #[test]
+    fn a_const_assertion_is_a_module_item_rather_than_a_test() {
+        parsing(Input(
+            r##"
+                $const "header is 16 bytes" size_of::<Header>() $eq 16;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                const _: () = assert!(size_of::<Header>() == 16, "header is 16 bytes");
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_const_assertion_can_be_a_plain_expression() {
+        parsing(Input(
+            r##"
+                $const "fits in a page" HEADER_SIZE + BODY_SIZE <= 4096;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                const _: () = assert!(HEADER_SIZE + BODY_SIZE <= 4096, "fits in a page");
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_const_assertion_can_use_any_comparison() {
+        parsing(Input(
+            r##"
+                $const "ne" A $ne 1;
+                $const "lt" A $lt 2;
+                $const "le" A $le 3;
+                $const "gt" A $gt 4;
+                $const "ge" A $ge 5;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                const _: () = assert!(A != 1, "ne");
+                const _: () = assert!(A < 2, "lt");
+                const _: () = assert!(A <= 3, "le");
+                const _: () = assert!(A > 4, "gt");
+                const _: () = assert!(A >= 5, "ge");
+            "##,
+        ));
+    }
+
+    #[test]
+    fn const_assertions_are_generated_ahead_of_the_tests_and_without_their_setup() {
+        parsing(Input(
+            r##"
+                $"the header" {
+                    let header = Header::default();
+                    $"starts empty" header.is_empty();
+                    $const "is 16 bytes" size_of::<Header>() $eq 16;
+                }
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                const _: () = assert!(size_of::<Header>() == 16, "is 16 bytes");
+                #[test]
+                fn the_header_starts_empty() {
+                    let header = Header::default();
+                    assert!(header.is_empty());
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_const_assertion_names_the_suite() {
+        spoke::generate_tests(
+            Input(
+                r##"
+                    $const "header is 16 bytes" size_of::<Header>() $eq 16;
+                "##,
+            )
+            .stream(),
+        )
+        .matches(Expected(
+            r##"
+                #[cfg(test)]
+                #[allow(unused_mut)]
+                #[allow(unused_variables)]
+                mod spoketest_header_is_16_bytes_9302a676 {
+                    #[allow(unused_imports)]
+                    use super::*;
+                    const _: () = assert!(size_of::<Header>() == 16, "header is 16 bytes");
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_const_assertion_needs_a_requirement_in_quotes() {
+        parsing(Input(
+            r##"
+                $const size_of::<Header>() $eq 16;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("expected a requirement in quotes following `$const`, but found `size_of`");
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_const_assertion_needs_an_expression() {
+        parsing(Input(
+            r##"
+                $const "empty";
+                $const "left" $eq 16;
+                $const "right" A $eq;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("expected an expression following the requirement of the `$const` assertion, but found `;`");
+                compile_error!("no code found for the left side of the const assertion");
+                compile_error!("no code found for the right hand side of the const assertion");
+            "##,
+        ));
+    }
+
+    #[test]
+    fn only_comparisons_can_be_checked_at_compile_time() {
+        parsing(Input(
+            r##"
+                $const "result" parse() $ok 16;
+                $const "chained" A $eq 1 $onfail "message";
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("expected a comparison [eq,ne,lt,le,gt,ge] following the dollars of a `$const` assertion, but found `ok`");
+                compile_error!("expected `;` following the right hand side of the `$const` assertion, but found `$`");
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_const_assertion_cannot_be_marked() {
+        parsing(Input(
+            r##"
+                $ignore $const "header is 16 bytes" size_of::<Header>() $eq 16;
+                $"still parsed" true;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("a `$const` assertion is checked at compile time so it can't be marked");
+                #[test]
+                fn still_parsed() {
+                    assert!(true);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_const_assertion_ends_the_preamble() {
+        spoke::generate_tests(
+            Input(
+                r##"
+                    $const "header is 16 bytes" size_of::<Header>() $eq 16;
+                    $config(module = layout);
+                "##,
+            )
+            .stream(),
+        )
+        .matches(Expected(
+            r##"
+                #[cfg(test)]
+                #[allow(unused_mut)]
+                #[allow(unused_variables)]
+                mod spoketest_header_is_16_bytes_dc958707 {
+                    #[allow(unused_imports)]
+                    use super::*;
+                    compile_error!("`$config` is only allowed in the suite preamble, before any tests are defined");
+                    const _: () = assert!(size_of::<Header>() == 16, "header is 16 bytes");
+                }
+            "##,
+        ));
+    }
+}
@@ -0,0 +1,258 @@
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Span;
+
+    #[allow(unused_imports)]
+    use super::*;
+
+    use crate::{name::*, parse, parser::*, unit_tests::testing_helpers::*};
+
+    struct SuiteStructure();
+    impl SurroundingString for SuiteStructure {
+        fn surround(input: &str) -> String {
+            format!(
+                "#[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]  mod spoketest {{ #[allow(unused_imports)] use super::*; {} }}",
+                input
+            )
+        }
+    }
+
+    #[test]
+    fn each_row_produces_its_own_test() {
+        parsing(Input(
+            r##"
+                $(a, b, c)
+                 [3, 4, 12]
+                 [0, 10, 0]
+                "given {a} and {b} as input, the output is expected to be {c}" a*b $eq c;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn given_3_and_4_as_input_comma_the_output_is_expected_to_be_12() {
+                    let a = 3; let b = 4; let c = 12;
+                    assert_eq!(a*b, c);
+                }
+                #[test]
+                fn given_0_and_10_as_input_comma_the_output_is_expected_to_be_0() {
+                    let a = 0; let b = 10; let c = 0;
+                    assert_eq!(a*b, c);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn typed_columns_produce_typed_bindings() {
+        parsing(Input(
+            r##"
+                $(a: u8, b: Vec<u8>)
+                 [1, vec![1]]
+                "typed {a}" b.len() $eq a as usize;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn typed_1() {
+                    let a: u8 = 1; let b: Vec<u8> = vec![1];
+                    assert_eq!(b.len(), a as usize);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn typed_columns_can_have_generic_types_with_several_parameters() {
+        parsing(Input(
+            r##"
+                $(m: HashMap<u8, u8>, f: fn(u8, u8) -> u8, n: usize)
+                 [HashMap::new(), max, 0]
+                "empty {n}" m.len() $eq n;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn empty_0() {
+                    let m: HashMap<u8, u8> = HashMap::new(); let f: fn(u8, u8) -> u8 = max; let n: usize = 0;
+                    assert_eq!(m.len(), n);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn values_can_hold_turbofish_generics_closures_and_comparisons() {
+        parsing(Input(
+            r##"
+                $(m, f, small)
+                 [HashMap::<u8, u8>::new(), |a: u8, b: u8| a + b, 1 < 2]
+                "mixed values" f(m.len() as u8, 1) $eq small as u8;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn mixed_values() {
+                    let m = HashMap::<u8, u8>::new(); let f = |a: u8, b: u8| a + b; let small = 1 < 2;
+                    assert_eq!(f(m.len() as u8, 1), small as u8);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn rows_with_the_same_name_are_told_apart_by_their_position() {
+        parsing(Input(
+            r##"
+                $(a)
+                 [Blob()]
+                 [Thing()]
+                "using inference" a.is_valid();
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn using_inference_row_1() {
+                    let a = Blob();
+                    assert!(a.is_valid());
+                }
+                #[test]
+                fn using_inference_row_2() {
+                    let a = Thing();
+                    assert!(a.is_valid());
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn rows_are_bound_after_the_setup_of_the_surrounding_body() {
+        parse_valid(Input(
+            r##"
+                let s = String::new();
+                $(word)
+                 ["hello"]
+                 ["world",]
+                "{word}" {
+                    let t = s.clone() + word;
+                    $"is not empty" !t.is_empty();
+                }
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn a_test_hello_is_not_empty() {
+                    let s = String::new();
+                    let word = "hello";
+                    let t = s.clone() + word;
+                    assert!(!t.is_empty());
+                }
+                #[test]
+                fn a_test_world_is_not_empty() {
+                    let s = String::new();
+                    let word = "world";
+                    let t = s.clone() + word;
+                    assert!(!t.is_empty());
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn rows_with_the_wrong_number_of_values_are_an_error() {
+        parsing(Input(
+            r##"
+                $(a, b)
+                 [1]
+                 [1, 2]
+                "sum of {a} and {b}" a + b $eq 3;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("expected 2 values in the data row to match the data table header, but found 1");
+                #[test]
+                fn sum_of_1_and_2() {
+                    let a = 1; let b = 2;
+                    assert_eq!(a + b, 3);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_table_without_rows_is_an_error() {
+        parsing(Input(
+            r##"
+                $(a) "nothing" a;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("expected at least one data row in brackets between the data table and the test name");
+            "##,
+        ));
+    }
+
+    #[test]
+    fn columns_must_be_named() {
+        parsing(Input(
+            r##"
+                $(a, 7) [1, 2] "bad header" a;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("expected a variable name for each column of the data table, but found `7`");
+                compile_error!("expected 1 values in the data row to match the data table header, but found 2");
+                compile_error!("expected at least one data row in brackets between the data table and the test name");
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_table_must_be_followed_by_a_name() {
+        parsing(Input(
+            r##"
+                $(a) [1] a $eq 1;
+                $"next" true;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("expected a data row in brackets or a test name in quotes following the data table, but found `a`");
+                #[test]
+                fn next() {
+                    assert!(true);
+                }
+            "##,
+        ));
+    }
+
+    fn parse_valid(input: Input) -> proc_macro2::TokenStream {
+        let mut output = SuiteGenerator::new();
+
+        let tok = Input(format!("{{ {} }}", input.0).as_str())
+            .stream()
+            .into_iter()
+            .next()
+            .expect("there should be valid input");
+
+        match tok {
+            proc_macro2::TokenTree::Group(group) => parse::Body::generate_body_in_suite(
+                parse::Suite(),
+                Name::new(&Span::call_site(), "a_test"),
+                group,
+                &mut output,
+            ),
+            _ => panic!("body parsers can only parse groups"),
+        };
+
+        output.generate_output()
+    }
+}
@@ -0,0 +1,269 @@
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Span;
+
+    #[allow(unused_imports)]
+    use super::*;
+
+    use crate::{fixtures::support_module, unit_tests::testing_helpers::*};
+
+    struct SuiteStructure();
+    impl SurroundingString for SuiteStructure {
+        fn surround(input: &str) -> String {
+            format!(
+                "#[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]  mod spoketest {{ #[allow(unused_imports)] use super::*; {} }}",
+                input
+            )
+        }
+    }
+
+    struct FixturesSuiteStructure();
+    impl SurroundingString for FixturesSuiteStructure {
+        fn surround(input: &str) -> String {
+            // the support module follows any errors and the preamble, just before the tests
+            let (preamble, tests) = input.split_at(input.find("#[test]").unwrap_or(input.len()));
+            format!(
+                "#[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]  mod spoketest {{ #[allow(unused_imports)] use super::*; {} {} {} }}",
+                preamble,
+                support_module(Span::call_site()),
+                tests
+            )
+        }
+    }
+
+    #[test]
+    fn a_fork_clones_its_locals_into_each_test() {
+        parsing(Input(
+            r##"
+                $"a grammar" {
+                    $fork {
+                        let mut grammar = Grammar::build();
+                        let start = grammar.start();
+                        $"has rules" !grammar.is_empty();
+                        $"with a new rule" {
+                            grammar.add(start);
+                            $"has the rule" grammar.contains(start);
+                        }
+                    }
+                }
+            "##,
+        ))
+        .matches_inside::<FixturesSuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn a_grammar_has_rules() {
+                    let (mut grammar, start,) = spoke_fixtures::forked(1, || {
+                        let mut grammar = Grammar::build();
+                        let start = grammar.start();
+                        (grammar, start,)
+                    });
+                    assert!(!grammar.is_empty());
+                }
+                #[test]
+                fn a_grammar_with_a_new_rule_has_the_rule() {
+                    let (mut grammar, start,) = spoke_fixtures::forked(1, || {
+                        let mut grammar = Grammar::build();
+                        let start = grammar.start();
+                        (grammar, start,)
+                    });
+                    grammar.add(start);
+                    assert!(grammar.contains(start));
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn code_between_the_tests_of_a_fork_gives_the_later_tests_their_own_state() {
+        parsing(Input(
+            r##"
+                $fork {
+                    let mut stack = vec![1];
+                    $"has one" stack.len() $eq 1;
+                    stack.push(2);
+                    $"has two" stack.len() $eq 2;
+                }
+            "##,
+        ))
+        .matches_inside::<FixturesSuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn has_one() {
+                    let (mut stack,) = spoke_fixtures::forked(1, || {
+                        let mut stack = vec![1];
+                        (stack,)
+                    });
+                    assert_eq!(stack.len(), 1);
+                }
+                #[test]
+                fn has_two() {
+                    let (mut stack,) = spoke_fixtures::forked(2, || {
+                        let mut stack = vec![1];
+                        stack.push(2);
+                        (stack,)
+                    });
+                    assert_eq!(stack.len(), 2);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_shadowed_local_is_only_cloned_once() {
+        parsing(Input(
+            r##"
+                $fork {
+                    let (mut count, total): (usize, usize) = sizes();
+                    let _ = warm_up();
+                    let count = count + 1;
+                    let Point { x, y: height } = origin();
+                    let height = height * 2;
+                    $"counts" count $eq total;
+                }
+            "##,
+        ))
+        .matches_inside::<FixturesSuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn counts() {
+                    let (total, count, x, height,) = spoke_fixtures::forked(1, || {
+                        let (mut count, total): (usize, usize) = sizes();
+                        let _ = warm_up();
+                        let count = count + 1;
+                        let Point { x, y: height } = origin();
+                        let height = height * 2;
+                        (total, count, x, height,)
+                    });
+                    assert_eq!(count, total);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_fork_only_clones_the_names_bound_by_rest_patterns() {
+        parsing(Input(
+            r##"
+                $fork {
+                    let Point { x, .. } = origin();
+                    let [first, .., mut last] = corners();
+                    $"is square" x + first $eq last;
+                }
+            "##,
+        ))
+        .matches_inside::<FixturesSuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn is_square() {
+                    let (x, first, mut last,) = spoke_fixtures::forked(1, || {
+                        let Point { x, .. } = origin();
+                        let [first, .., mut last] = corners();
+                        (x, first, last,)
+                    });
+                    assert_eq!(x + first, last);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_fork_clones_the_value_of_a_ref_binding() {
+        parsing(Input(
+            r##"
+                $fork {
+                    let Config { ref name, .. } = load();
+                    $"is named" !name.is_empty();
+                }
+            "##,
+        ))
+        .matches_inside::<FixturesSuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn is_named() {
+                    let (ref name,) = spoke_fixtures::forked(1, || {
+                        let Config { ref name, .. } = load();
+                        (name.clone(),)
+                    });
+                    assert!(!name.is_empty());
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn teardown_in_a_fork_follows_the_cloned_state() {
+        parsing(Input(
+            r##"
+                $fork {
+                    let dir = TempDir::new();
+                    $after { dir.clean(); }
+                    $"is empty" dir.is_empty();
+                }
+            "##,
+        ))
+        .matches_inside::<FixturesSuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn is_empty() {
+                    let (dir,) = spoke_fixtures::forked(1, || {
+                        let dir = TempDir::new();
+                        (dir,)
+                    });
+                    let spoke_outcome = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| {
+                        assert!(dir.is_empty());
+                    }));
+                    { dir.clean(); }
+                    if let ::std::result::Result::Err(spoke_panic) = spoke_outcome {
+                        ::std::panic::resume_unwind(spoke_panic);
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_fork_without_code_is_a_plain_block() {
+        parsing(Input(
+            r##"
+                $"a value" {
+                    let value = 1;
+                    $fork {
+                        $"is one" value $eq 1;
+                    }
+                }
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn a_value_is_one() {
+                    let value = 1;
+                    assert_eq!(value, 1);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_fork_needs_a_braced_block_of_tests() {
+        parsing(Input(
+            r##"
+                $"a grammar" {
+                    $fork let grammar = build();
+                    $fork { let grammar = build(); }
+                    $"still parsed" true;
+                }
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("expected a braced block of tests following `$fork`, but found `let`");
+                compile_error!("expected at least one test inside the `$fork` block");
+                #[test]
+                fn a_grammar_still_parsed() {
+                    assert!(true);
+                }
+            "##,
+        ));
+    }
+}
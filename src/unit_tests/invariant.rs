@@ -0,0 +1,174 @@
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    use crate::unit_tests::testing_helpers::*;
+
+    struct SuiteStructure();
+    impl SurroundingString for SuiteStructure {
+        fn surround(input: &str) -> String {
+            format!(
+                "#[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]  mod spoketest {{ #[allow(unused_imports)] use super::*; {} }}",
+                input
+            )
+        }
+    }
+
+    #[test]
+    fn an_invariant_is_checked_after_the_assertion_of_each_following_test() {
+        parsing(Input(
+            r##"
+                $"an account" {
+                    let mut account = Account::new(10);
+                    $"starts open" account.is_open();
+                    $invariant "balance is never negative" account.balance() >= 0;
+                    $"withdrawing" {
+                        account.withdraw(5);
+                        $"leaves five" account.balance() $eq 5;
+                    }
+                }
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn an_account_starts_open() {
+                    let mut account = Account::new(10);
+                    assert!(account.is_open());
+                }
+                #[test]
+                fn an_account_withdrawing_leaves_five() {
+                    let mut account = Account::new(10);
+                    account.withdraw(5);
+                    assert_eq!(account.balance(), 5);
+                    assert!(account.balance() >= 0, "invariant `{}` does not hold: {}", "balance is never negative", "account . balance () >= 0");
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn invariants_are_checked_outermost_first() {
+        parsing(Input(
+            r##"
+                $"a list" {
+                    let mut list = List::new();
+                    $invariant "is sorted" list.is_sorted();
+                    $"with items" {
+                        list.insert(3);
+                        $invariant "is not empty" !list.is_empty();
+                        $"has one" list.len() $eq 1;
+                    }
+                }
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn a_list_with_items_has_one() {
+                    let mut list = List::new();
+                    list.insert(3);
+                    assert_eq!(list.len(), 1);
+                    assert!(list.is_sorted(), "invariant `{}` does not hold: {}", "is sorted", "list . is_sorted ()");
+                    assert!(!list.is_empty(), "invariant `{}` does not hold: {}", "is not empty", "! list . is_empty ()");
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn invariants_are_checked_before_the_teardown() {
+        parsing(Input(
+            r##"
+                $"a file" {
+                    let file = open();
+                    $after { file.close(); }
+                    $invariant "is readable" file.is_readable();
+                    $"has a size" file.size() $eq 4;
+                }
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn a_file_has_a_size() {
+                    let file = open();
+                    let spoke_outcome = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| {
+                        assert_eq!(file.size(), 4);
+                        assert!(file.is_readable(), "invariant `{}` does not hold: {}", "is readable", "file . is_readable ()");
+                    }));
+                    { file.close(); }
+                    if let ::std::result::Result::Err(spoke_panic) = spoke_outcome {
+                        ::std::panic::resume_unwind(spoke_panic);
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn an_invariant_needs_a_requirement_and_a_condition() {
+        parsing(Input(
+            r##"
+                $"an account" {
+                    $invariant balance >= 0;
+                    $invariant "never negative";
+                    $"still parsed" true;
+                }
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("expected a requirement in quotes following `$invariant`, but found `balance`");
+                compile_error!("expected a condition following the requirement of the `$invariant`, but found `;`");
+                #[test]
+                fn an_account_still_parsed() {
+                    assert!(true);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn an_invariant_is_a_plain_condition() {
+        parsing(Input(
+            r##"
+                $"an account" {
+                    $invariant "never negative" balance $ge 0;
+                    $"still parsed" true;
+                }
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("an `$invariant` is a plain condition so it can't be followed by further dollars");
+                #[test]
+                fn an_account_still_parsed() {
+                    assert!(true);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn an_invariant_cannot_be_marked() {
+        parsing(Input(
+            r##"
+                $"an account" {
+                    $ignore $invariant "never negative" balance >= 0;
+                    $"still parsed" true;
+                }
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("`$invariant` applies to all of the tests that follow it so it can't be marked");
+                #[test]
+                fn an_account_still_parsed() {
+                    assert!(true);
+                }
+            "##,
+        ));
+    }
+}
@@ -0,0 +1,267 @@
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    use proc_macro2::Span;
+
+    use crate::{power_assert, unit_tests::testing_helpers::*};
+
+    struct SuiteStructure();
+    impl SurroundingString for SuiteStructure {
+        fn surround(input: &str) -> String {
+            format!(
+                "#[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]  mod spoketest {{ #[allow(unused_imports)] use super::*; {} }}",
+                input
+            )
+        }
+    }
+
+    struct PowerSuiteStructure();
+    impl SurroundingString for PowerSuiteStructure {
+        fn surround(input: &str) -> String {
+            format!(
+                "#[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]  mod spoketest {{ #[allow(unused_imports)] use super::*; {} {} }}",
+                power_assert::support_module(Span::call_site()),
+                input
+            )
+        }
+    }
+
+    #[test]
+    fn an_assertion_can_be_ignored() {
+        parsing(Input(
+            r##"
+                $ignore "no items in cart" basket.is_empty();
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                #[ignore]
+                fn no_items_in_cart() {
+                    assert!(basket.is_empty());
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn an_ignored_test_can_have_a_reason() {
+        parsing(Input(
+            r##"
+                $ignore."too slow" "no items in cart" basket.is_empty();
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                #[ignore = "too slow"]
+                fn no_items_in_cart() {
+                    assert!(basket.is_empty());
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn ignoring_a_body_ignores_every_test_inside_it() {
+        parsing(Input(
+            r##"
+                $ignore "network" {
+                    let client = Client::new();
+                    $"connects" client.connect();
+                    $"disconnects" {
+                        client.connect();
+                        $"cleanly" client.disconnect();
+                    }
+                }
+                $"not ignored" true;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                #[ignore]
+                fn network_connects() {
+                    let client = Client::new();
+                    assert!(client.connect());
+                }
+                #[test]
+                #[ignore]
+                fn network_disconnects_cleanly() {
+                    let client = Client::new();
+                    client.connect();
+                    assert!(client.disconnect());
+                }
+                #[test]
+                fn not_ignored() {
+                    assert!(true);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn the_innermost_ignore_reason_is_used() {
+        parsing(Input(
+            r##"
+                $ignore."outer" "network" {
+                    $ignore."inner" "connects" true;
+                }
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                #[ignore = "inner"]
+                fn network_connects() {
+                    assert!(true);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_marker_can_be_followed_by_further_dollars() {
+        parsing(Input(
+            r##"
+                $ignore $(a) [1] "row {a}" a > 0;
+            "##,
+        ))
+        .matches_inside::<PowerSuiteStructure>(Expected(
+            r##"
+                #[test]
+                #[ignore]
+                fn row_1() {
+                    let a = 1;
+                    {
+                        #[allow(unused_imports)]
+                        use spoke_power::{ViaDebug as _, WithoutDebug as _};
+                        let mut spoke_values = spoke_power::Values::new();
+                        let spoke_passed = match (&(a), &(0)) {
+                            (spoke_left, spoke_right) => {
+                                spoke_values.record("a > 0", format!("{} > {}", (&spoke_power::Value(spoke_left)).spoke_debug(), (&spoke_power::Value(spoke_right)).spoke_debug()));
+                                *spoke_left > *spoke_right
+                            }
+                        };
+                        assert!(spoke_passed, "assertion failed: {}\n{}", "a > 0", spoke_values);
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn an_ignore_reason_must_be_quoted() {
+        parsing(Input(
+            r##"
+                $ignore.slow "test" true;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("expected a reason in quotes following `ignore.`, but found `slow`");
+                #[test]
+                #[ignore]
+                fn test() {
+                    assert!(true);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_marker_must_be_followed_by_a_name() {
+        parsing(Input(
+            r##"
+                $ignore { $"inner" true; }
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("expected a test name in quotes following the ignore marker, but found `{ $ \"inner\" true ; }`");
+                #[test]
+                #[ignore]
+                fn missing_name_inner() {
+                    assert!(true);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_pending_test_passes_only_while_it_fails() {
+        parsing(Input(
+            r##"
+                $pending "no items in cart" basket.is_empty();
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn no_items_in_cart() {
+                    let spoke_pending = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| {
+                        assert!(basket.is_empty());
+                    }));
+                    assert!(spoke_pending.is_err(), "this pending test now passes, remove $pending");
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_pending_test_can_be_gated_behind_a_feature() {
+        parsing(Input(
+            r##"
+                $pending.known_bugs "cart" {
+                    let basket = Basket::new();
+                    $"is empty" basket.is_empty();
+                }
+                $pending."known-bugs" "quoted feature" true;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                #[cfg(feature = "known_bugs")]
+                fn cart_is_empty() {
+                    let spoke_pending = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| {
+                        let basket = Basket::new();
+                        assert!(basket.is_empty());
+                    }));
+                    assert!(spoke_pending.is_err(), "this pending test now passes, remove $pending");
+                }
+                #[test]
+                #[cfg(feature = "known-bugs")]
+                fn quoted_feature() {
+                    let spoke_pending = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| {
+                        assert!(true);
+                    }));
+                    assert!(spoke_pending.is_err(), "this pending test now passes, remove $pending");
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_pending_feature_must_be_a_name() {
+        parsing(Input(
+            r##"
+                $pending.7 "test" true;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("expected a feature name following `pending.`, but found `7`");
+                #[test]
+                fn test() {
+                    let spoke_pending = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| {
+                        assert!(true);
+                    }));
+                    assert!(spoke_pending.is_err(), "this pending test now passes, remove $pending");
+                }
+            "##,
+        ));
+    }
+}
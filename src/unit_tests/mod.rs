@@ -1,5 +1,20 @@
+mod after;
+mod approx;
 mod asserts;
+mod asynchronous;
+mod backend;
 mod body;
+mod collections;
+mod config;
+mod const_assert;
+mod data_table;
+mod fork;
+mod invariant;
+mod markers;
+mod module_claims;
 mod suite;
 mod testing_helpers;
+mod type_check;
 mod name;
+mod power_assert;
+mod shared_fixture;
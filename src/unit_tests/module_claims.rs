@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use proc_macro2::{Ident, Span};
+
+    #[allow(unused_imports)]
+    use super::*;
+
+    use crate::module_claims::*;
+
+    fn module(name: &str) -> Ident {
+        Ident::new(name, Span::call_site())
+    }
+
+    fn at(line: usize, fingerprint: u32) -> Claim {
+        Claim {
+            line,
+            column: 0,
+            fingerprint,
+        }
+    }
+
+    #[test]
+    fn a_module_can_be_claimed_once() {
+        let mut claims = ModuleClaims::new();
+
+        assert_eq!(
+            claims.claim(PathBuf::from("lib.rs"), &module("tests"), at(1, 1)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn a_second_suite_claiming_the_same_module_is_rejected() {
+        let mut claims = ModuleClaims::new();
+        let _ = claims.claim(PathBuf::from("lib.rs"), &module("tests"), at(1, 1));
+
+        assert_eq!(
+            claims.claim(PathBuf::from("lib.rs"), &module("tests"), at(20, 2)),
+            Err(at(1, 1))
+        );
+    }
+
+    #[test]
+    fn the_same_module_can_be_used_in_different_files() {
+        let mut claims = ModuleClaims::new();
+        let _ = claims.claim(PathBuf::from("lib.rs"), &module("tests"), at(1, 1));
+
+        assert_eq!(
+            claims.claim(PathBuf::from("other.rs"), &module("tests"), at(20, 2)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn different_modules_can_be_claimed_in_the_same_file() {
+        let mut claims = ModuleClaims::new();
+        let _ = claims.claim(PathBuf::from("lib.rs"), &module("tests"), at(1, 1));
+
+        assert_eq!(
+            claims.claim(PathBuf::from("lib.rs"), &module("more_tests"), at(20, 2)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn expanding_the_same_suite_again_is_not_a_collision() {
+        let mut claims = ModuleClaims::new();
+        let _ = claims.claim(PathBuf::from("lib.rs"), &module("tests"), at(1, 1));
+
+        assert_eq!(
+            claims.claim(PathBuf::from("lib.rs"), &module("tests"), at(1, 2)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn an_unchanged_suite_that_moved_is_not_a_collision() {
+        let mut claims = ModuleClaims::new();
+        let _ = claims.claim(PathBuf::from("lib.rs"), &module("tests"), at(1, 1));
+        let _ = claims.claim(PathBuf::from("lib.rs"), &module("tests"), at(5, 1));
+
+        assert_eq!(
+            claims.claim(PathBuf::from("lib.rs"), &module("tests"), at(9, 3)),
+            Err(at(5, 1))
+        );
+    }
+
+    #[test]
+    fn a_collision_names_the_suite_that_claimed_the_module() {
+        let mut claims = ModuleClaims::new();
+        let _ = claims.claim(PathBuf::from("lib.rs"), &module("tests"), at(12, 1));
+        let claim = claims
+            .claim(PathBuf::from("lib.rs"), &module("tests"), at(40, 2))
+            .unwrap_err();
+
+        assert_eq!(
+            collision(&module("tests"), claim),
+            "the module `tests` is already generated by the spoke::test! at line 12 of this file, use `$config(module = name)` to choose a different name"
+        );
+    }
+}
@@ -453,4 +453,30 @@ mod tests {
             name.function_name().0
         )
     }
+
+    #[test]
+    fn names_can_be_created_from_code() {
+        let code = "basket.is_empty()"
+            .parse::<proc_macro2::TokenStream>()
+            .unwrap()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let names = [Name::from_code(&Span::call_site(), &code)];
+        let name = CompoundName::new().followed_by(&names[0]);
+
+        assert_eq!("basket_dot_is_empty_parens", name.function_name().0)
+    }
+
+    #[test]
+    fn names_created_from_code_drop_the_dollars() {
+        let code = "v.len() $eq 3"
+            .parse::<proc_macro2::TokenStream>()
+            .unwrap()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let names = [Name::from_code(&Span::call_site(), &code)];
+        let name = CompoundName::new().followed_by(&names[0]);
+
+        assert_eq!("v_dot_len_parens_eq_3", name.function_name().0)
+    }
 }
@@ -0,0 +1,337 @@
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Span;
+
+    #[allow(unused_imports)]
+    use super::*;
+
+    use crate::{
+        name::*, parse, parser::*, power_assert::support_module, unit_tests::testing_helpers::*,
+    };
+
+    struct SuiteStructure();
+    impl SurroundingString for SuiteStructure {
+        fn surround(input: &str) -> String {
+            format!(
+                "#[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]  mod spoketest {{ #[allow(unused_imports)] use super::*; {} {} }}",
+                support_module(Span::call_site()),
+                input
+            )
+        }
+    }
+
+    // an expression with nothing to take apart is left to assert!
+    struct PlainSuiteStructure();
+    impl SurroundingString for PlainSuiteStructure {
+        fn surround(input: &str) -> String {
+            format!(
+                "#[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]  mod spoketest {{ #[allow(unused_imports)] use super::*; {} }}",
+                input
+            )
+        }
+    }
+
+    #[test]
+    fn a_comparison_reports_the_value_of_each_side() {
+        parse_valid(Input(
+            r##"
+                $"test" user.age() >= limit;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    {
+                        #[allow(unused_imports)]
+                        use spoke_power::{ViaDebug as _, WithoutDebug as _};
+                        let mut spoke_values = spoke_power::Values::new();
+                        let spoke_passed = match (&(user.age()), &(limit)) {
+                            (spoke_left, spoke_right) => {
+                                spoke_values.record("user . age () >= limit", format!("{} >= {}", (&spoke_power::Value(spoke_left)).spoke_debug(), (&spoke_power::Value(spoke_right)).spoke_debug()));
+                                *spoke_left >= *spoke_right
+                            }
+                        };
+                        assert!(spoke_passed, "assertion failed: {}\n{}", "user . age () >= limit", spoke_values);
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn boolean_operators_keep_their_short_circuiting() {
+        parse_valid(Input(
+            r##"
+                $"test" ready || cart.items.is_empty();
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    {
+                        #[allow(unused_imports)]
+                        use spoke_power::{ViaDebug as _, WithoutDebug as _};
+                        let mut spoke_values = spoke_power::Values::new();
+                        let spoke_passed = ({
+                            match ready {
+                                spoke_value => {
+                                    spoke_values.record("ready", format!("{:?}", spoke_value));
+                                    spoke_value
+                                }
+                            }
+                        }) || ({
+                            spoke_values.record("cart . items", (&spoke_power::Value(&cart.items)).spoke_debug());
+                            match cart.items.is_empty() {
+                                spoke_value => {
+                                    spoke_values.record("cart . items . is_empty ()", format!("{:?}", spoke_value));
+                                    spoke_value
+                                }
+                            }
+                        });
+                        assert!(spoke_passed, "assertion failed: {}\n{}", "ready || cart . items . is_empty ()", spoke_values);
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn and_binds_more_tightly_than_or() {
+        parse_valid(Input(
+            r##"
+                $"test" a || b && c;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    {
+                        #[allow(unused_imports)]
+                        use spoke_power::{ViaDebug as _, WithoutDebug as _};
+                        let mut spoke_values = spoke_power::Values::new();
+                        let spoke_passed = ({
+                            match a { spoke_value => { spoke_values.record("a", format!("{:?}", spoke_value)); spoke_value } }
+                        }) || (({
+                            match b { spoke_value => { spoke_values.record("b", format!("{:?}", spoke_value)); spoke_value } }
+                        }) && ({
+                            match c { spoke_value => { spoke_values.record("c", format!("{:?}", spoke_value)); spoke_value } }
+                        }));
+                        assert!(spoke_passed, "assertion failed: {}\n{}", "a || b && c", spoke_values);
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_negated_comparison_is_taken_apart() {
+        parse_valid(Input(
+            r##"
+                $"test" !(name == "admin");
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    {
+                        #[allow(unused_imports)]
+                        use spoke_power::{ViaDebug as _, WithoutDebug as _};
+                        let mut spoke_values = spoke_power::Values::new();
+                        let spoke_passed = !(match (&(name), &("admin")) {
+                            (spoke_left, spoke_right) => {
+                                spoke_values.record("name == \"admin\"", format!("{} == {}", (&spoke_power::Value(spoke_left)).spoke_debug(), (&spoke_power::Value(spoke_right)).spoke_debug()));
+                                *spoke_left == *spoke_right
+                            }
+                        });
+                        assert!(spoke_passed, "assertion failed: {}\n{}", "! (name == \"admin\")", spoke_values);
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn the_brackets_of_a_turbofish_are_not_comparisons() {
+        parse_valid(Input(
+            r##"
+                $"test" v.iter().collect::<Vec<_>>() != empty;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    {
+                        #[allow(unused_imports)]
+                        use spoke_power::{ViaDebug as _, WithoutDebug as _};
+                        let mut spoke_values = spoke_power::Values::new();
+                        let spoke_passed = match (&(v.iter().collect::<Vec<_>>()), &(empty)) {
+                            (spoke_left, spoke_right) => {
+                                spoke_values.record("v . iter () . collect ::< Vec < _ >> () != empty", format!("{} != {}", (&spoke_power::Value(spoke_left)).spoke_debug(), (&spoke_power::Value(spoke_right)).spoke_debug()));
+                                *spoke_left != *spoke_right
+                            }
+                        };
+                        assert!(spoke_passed, "assertion failed: {}\n{}", "v . iter () . collect ::< Vec < _ >> () != empty", spoke_values);
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_failure_message_is_reported_with_the_values() {
+        parse_valid(Input(
+            r##"
+                $"test" total > 0 $onfail "order {} is empty", id;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_test() {
+                    {
+                        #[allow(unused_imports)]
+                        use spoke_power::{ViaDebug as _, WithoutDebug as _};
+                        let mut spoke_values = spoke_power::Values::new();
+                        let spoke_passed = match (&(total), &(0)) {
+                            (spoke_left, spoke_right) => {
+                                spoke_values.record("total > 0", format!("{} > {}", (&spoke_power::Value(spoke_left)).spoke_debug(), (&spoke_power::Value(spoke_right)).spoke_debug()));
+                                *spoke_left > *spoke_right
+                            }
+                        };
+                        assert!(spoke_passed, "assertion failed: {}: {}\n{}", "total > 0", format_args!("order {} is empty", id), spoke_values);
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn an_expression_without_an_operator_is_a_plain_assert() {
+        parse_valid(Input(
+            r##"
+                $"method" cart.is_empty();
+                $"negated" !done;
+                $"grouped" v.iter().any(|x| *x > 5);
+            "##,
+        ))
+        .matches_inside::<PlainSuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_method() {
+                    assert!(cart.is_empty());
+                }
+                #[test]
+                fn inner_negated() {
+                    assert!(!done);
+                }
+                #[test]
+                fn inner_grouped() {
+                    assert!(v.iter().any(|x| *x > 5));
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn an_expression_that_cannot_be_split_reliably_is_a_plain_assert() {
+        parse_valid(Input(
+            r##"
+                $"if" x == if y { 1 } else { 2 };
+                $"closure" || a > b;
+                $"range" 0..3 == r;
+                $"qualified" <T as Default>::default() == t;
+            "##,
+        ))
+        .matches_inside::<PlainSuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_if() {
+                    assert!(x == if y { 1 } else { 2 });
+                }
+                #[test]
+                fn inner_closure() {
+                    assert!(|| a > b);
+                }
+                #[test]
+                fn inner_range() {
+                    assert!(0..3 == r);
+                }
+                #[test]
+                fn inner_qualified() {
+                    assert!(<T as Default>::default() == t);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn the_support_module_is_only_generated_once() {
+        parse_valid(Input(
+            r##"
+                $"first" a == b;
+                $"second" a != c;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn inner_first() {
+                    {
+                        #[allow(unused_imports)]
+                        use spoke_power::{ViaDebug as _, WithoutDebug as _};
+                        let mut spoke_values = spoke_power::Values::new();
+                        let spoke_passed = match (&(a), &(b)) {
+                            (spoke_left, spoke_right) => {
+                                spoke_values.record("a == b", format!("{} == {}", (&spoke_power::Value(spoke_left)).spoke_debug(), (&spoke_power::Value(spoke_right)).spoke_debug()));
+                                *spoke_left == *spoke_right
+                            }
+                        };
+                        assert!(spoke_passed, "assertion failed: {}\n{}", "a == b", spoke_values);
+                    }
+                }
+                #[test]
+                fn inner_second() {
+                    {
+                        #[allow(unused_imports)]
+                        use spoke_power::{ViaDebug as _, WithoutDebug as _};
+                        let mut spoke_values = spoke_power::Values::new();
+                        let spoke_passed = match (&(a), &(c)) {
+                            (spoke_left, spoke_right) => {
+                                spoke_values.record("a != c", format!("{} != {}", (&spoke_power::Value(spoke_left)).spoke_debug(), (&spoke_power::Value(spoke_right)).spoke_debug()));
+                                *spoke_left != *spoke_right
+                            }
+                        };
+                        assert!(spoke_passed, "assertion failed: {}\n{}", "a != c", spoke_values);
+                    }
+                }
+            "##,
+        ));
+    }
+
+    fn parse_valid(input: Input) -> proc_macro2::TokenStream {
+        let mut output = SuiteGenerator::new();
+
+        let tok = Input(format!("{{ {} }}", input.0).as_str())
+            .stream()
+            .into_iter()
+            .next()
+            .expect("there should be valid input");
+
+        match tok {
+            proc_macro2::TokenTree::Group(group) => parse::Body::generate_body_in_suite(
+                parse::Suite(),
+                Name::new(&Span::call_site(), "inner"),
+                group,
+                &mut output,
+            ),
+            _ => panic!("body parsers can only parse groups"),
+        };
+
+        output.generate_output()
+    }
+}
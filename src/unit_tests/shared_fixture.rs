@@ -0,0 +1,184 @@
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Span;
+
+    #[allow(unused_imports)]
+    use super::*;
+
+    use crate::{fixtures::support_module, unit_tests::testing_helpers::*};
+
+    struct SuiteStructure();
+    impl SurroundingString for SuiteStructure {
+        fn surround(input: &str) -> String {
+            format!(
+                "#[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]  mod spoketest {{ #[allow(unused_imports)] use super::*; {} }}",
+                input
+            )
+        }
+    }
+
+    struct FixturesSuiteStructure();
+    impl SurroundingString for FixturesSuiteStructure {
+        fn surround(input: &str) -> String {
+            // the support module follows any errors and the preamble, just before the tests
+            let (preamble, tests) = input.split_at(input.find("#[test]").unwrap_or(input.len()));
+            format!(
+                "#[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]  mod spoketest {{ #[allow(unused_imports)] use super::*; {} {} {} }}",
+                preamble,
+                support_module(Span::call_site()),
+                tests
+            )
+        }
+    }
+
+    #[test]
+    fn a_shared_fixture_is_borrowed_by_each_following_test() {
+        parsing(Input(
+            r##"
+                $"a database" {
+                    let name = "users";
+                    $shared let db = Database::start();
+                    $"has the table" db.has_table(name);
+                    $"is running" db.is_running();
+                }
+            "##,
+        ))
+        .matches_inside::<FixturesSuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn a_database_has_the_table() {
+                    let name = "users";
+                    let db = spoke_fixtures::shared(1, || { Database::start() });
+                    assert!(db.has_table(name));
+                }
+                #[test]
+                fn a_database_is_running() {
+                    let name = "users";
+                    let db = spoke_fixtures::shared(1, || { Database::start() });
+                    assert!(db.is_running());
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_shared_fixture_can_give_its_type() {
+        parsing(Input(
+            r##"
+                $"a pool" {
+                    $shared let pool: Pool<Postgres> = Pool::connect(URL);
+                    $"is open" pool.is_open();
+                }
+            "##,
+        ))
+        .matches_inside::<FixturesSuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn a_pool_is_open() {
+                    let pool = spoke_fixtures::shared::<Pool<Postgres> >(1, || { Pool::connect(URL) });
+                    assert!(pool.is_open());
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_shared_fixture_in_the_preamble_starts_every_test() {
+        parsing(Input(
+            r##"
+                use std::sync::Arc;
+                $shared let server = Server::start();
+                $"a client" {
+                    let client = server.connect();
+                    $shared let cache = Cache::warm();
+                    $"connects" client.is_connected();
+                }
+                $"an admin" server.admin().is_some();
+            "##,
+        ))
+        .matches_inside::<FixturesSuiteStructure>(Expected(
+            r##"
+                use std::sync::Arc;
+                #[test]
+                fn a_client_connects() {
+                    let server = spoke_fixtures::shared(1, || { Server::start() });
+                    let client = server.connect();
+                    let cache = spoke_fixtures::shared(2, || { Cache::warm() });
+                    assert!(client.is_connected());
+                }
+                #[test]
+                fn an_admin() {
+                    let server = spoke_fixtures::shared(1, || { Server::start() });
+                    assert!(server.admin().is_some());
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_shared_fixture_at_the_top_level_must_be_in_the_preamble() {
+        parsing(Input(
+            r##"
+                $"first" true;
+                $shared let server = Server::start();
+            "##,
+        ))
+        .matches_inside::<FixturesSuiteStructure>(Expected(
+            r##"
+                compile_error!("`$shared` is only allowed in the suite preamble, before any tests are defined, or in the body of a test");
+                #[test]
+                fn first() {
+                    assert!(true);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_shared_fixture_is_a_let_statement() {
+        parsing(Input(
+            r##"
+                $"a database" {
+                    $shared db = Database::start();
+                    $shared let = Database::start();
+                    $shared let db;
+                    $"still parsed" true;
+                }
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("expected `let` following `$shared`, but found `db`");
+                compile_error!("expected a name following `$shared let`");
+                compile_error!("expected `=` and an expression following the name of the `$shared` fixture");
+                #[test]
+                fn a_database_still_parsed() {
+                    assert!(true);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn a_shared_fixture_cannot_be_marked_or_followed_by_dollars() {
+        parsing(Input(
+            r##"
+                $"a database" {
+                    $ignore $shared let db = Database::start();
+                    $shared let db = Database::start() $onfail "no database";
+                    $"still parsed" true;
+                }
+            "##,
+        ))
+        .matches_inside::<FixturesSuiteStructure>(Expected(
+            r##"
+                compile_error!("`$shared` applies to all of the tests that follow it so it can't be marked");
+                compile_error!("a `$shared` fixture is created once for all of its tests so it can't be followed by further dollars");
+                #[test]
+                fn a_database_still_parsed() {
+                    assert!(true);
+                }
+            "##,
+        ));
+    }
+}
@@ -3,7 +3,9 @@ mod tests {
     #[allow(unused_imports)]
     use super::*;
 
-    use crate::{spoke, unit_tests::testing_helpers::*};
+    use proc_macro2::Span;
+
+    use crate::{power_assert, spoke, unit_tests::testing_helpers::*};
 
     #[test]
     fn an_empty_input_produces_no_output() {
@@ -29,7 +31,9 @@ mod tests {
             #[cfg(test)]
             #[allow(unused_mut)]
             #[allow(unused_variables)] 
-            mod spoketest {
+            mod spoketest_first_test_5625bf05 {
+                #[allow(unused_imports)]
+                use super::*;
                 #[test] fn first_test() { }
             }
             "##,
@@ -50,7 +54,9 @@ mod tests {
             #[cfg(test)]
             #[allow (unused_mut)]
             #[allow (unused_variables)] 
-            mod spoketest {
+            mod spoketest_my_test_00ec7ad1 {
+                #[allow(unused_imports)]
+                use super::*;
                 #[test] fn my_test() { }
                 #[test] fn another_test() { }
                 #[test] fn t3rd_test() { }
@@ -59,6 +65,89 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn the_module_is_named_after_the_first_top_level_test() {
+        parsing(Input(
+            r##"
+               $"a vector" {
+                   $"starts empty" true;
+               }
+               $"another test"{}
+            "##,
+        ))
+        .matches(Expected(
+            r##"
+            #[cfg(test)]
+            #[allow (unused_mut)]
+            #[allow (unused_variables)] 
+            mod spoketest_a_vector_b845e3dd {
+                #[allow(unused_imports)]
+                use super::*;
+                #[test] fn a_vector_starts_empty() { assert!(true); }
+                #[test] fn another_test() { }
+            }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn suites_starting_with_the_same_test_are_given_different_modules() {
+        let module = |input: &str| {
+            parsing(Input(input))
+                .into_iter()
+                .skip_while(|token| token.to_string() != "mod")
+                .nth(1)
+                .map(|token| token.to_string())
+        };
+
+        let first = module(r#"$"a vector" { $"starts empty" true; }"#);
+        let second = module(r#"$"a vector" { $"has no length" true; }"#);
+
+        assert_ne!(first, second);
+        assert_eq!(first, module(r#"$"a vector" { $"starts empty" true; }"#));
+    }
+
+    const POSITIVE_CHECK: &str = r##"
+        {
+            #[allow(unused_imports)]
+            use spoke_power::{ViaDebug as _, WithoutDebug as _};
+            let mut spoke_values = spoke_power::Values::new();
+            let spoke_passed = match (&(x), &(0)) {
+                (spoke_left, spoke_right) => {
+                    spoke_values.record("x > 0", format!("{} > {}", (&spoke_power::Value(spoke_left)).spoke_debug(), (&spoke_power::Value(spoke_right)).spoke_debug()));
+                    *spoke_left > *spoke_right
+                }
+            };
+            assert!(spoke_passed, "assertion failed: {}\n{}", "x > 0", spoke_values);
+        }
+    "##;
+
+    #[test]
+    fn the_module_name_is_taken_from_a_top_level_data_table() {
+        parsing(Input(
+            r##"
+               $(x) [1] [2] "{x} is positive" x > 0;
+            "##,
+        ))
+        .matches(Expected(&format!(
+            r##"
+            #[cfg(test)]
+            #[allow (unused_mut)]
+            #[allow (unused_variables)] 
+            mod spoketest_t1_is_positive_c8999f7f {{
+                #[allow(unused_imports)]
+                use super::*;
+                {}
+                #[test] fn t1_is_positive() {{ let x = 1; {} }}
+                #[test] fn t2_is_positive() {{ let x = 2; {} }}
+            }}
+            "##,
+            power_assert::support_module(Span::call_site()),
+            POSITIVE_CHECK,
+            POSITIVE_CHECK,
+        )));
+    }
+
     #[test]
     fn preamble_is_included() {
         parsing(Input(
@@ -77,7 +166,9 @@ mod tests {
             #[cfg(test)]
             #[allow(unused_mut)]
             #[allow(unused_variables)] 
-                mod spoketest {
+                mod spoketest_first_test_aac098f5 {
+                    #[allow(unused_imports)]
+                    use super::*;
                 use mycrate::*;
                 use crate::deeply::nested::{
                     my_first_function,
@@ -111,7 +202,9 @@ mod tests {
                 #[cfg(test)]
                 #[allow (unused_mut)]
                 #[allow (unused_variables)] 
-                mod spoketest {        
+                mod spoketest_my_test_35c937e1 {        
+                    #[allow(unused_imports)]
+                    use super::*;
                     use mycrate::*;
                     use crate::deeply::nested::{
                         my_first_function,
@@ -150,7 +243,9 @@ mod tests {
                 #[cfg(test)]
                 #[allow (unused_mut)]
                 #[allow (unused_variables)] 
-                mod spoketest {        
+                mod spoketest_my_test_734a9d91 {        
+                    #[allow(unused_imports)]
+                    use super::*;
                     use mycrate::*;
                     use crate::deeply::nested::{
                         my_first_function,
@@ -182,6 +277,8 @@ mod tests {
             #[allow(unused_mut)]
             #[allow(unused_variables)] 
             mod spoketest {
+                #[allow(unused_imports)]
+                use super::*;
                 compile_error!("expected a valid test case name in quotes following the dollars, but found `\"first test\"uhoh`\nunmatched suffix detected, `uhoh`, did you miss a space?");
                 #[test] fn missing_name() { }
             }
@@ -202,6 +299,8 @@ mod tests {
             #[allow(unused_mut)]
             #[allow(unused_variables)] 
             mod spoketest {
+                #[allow(unused_imports)]
+                use super::*;
                 compile_error!("expected a valid test case name in quotes following the dollars, but found `first`");
                 #[test] fn missing_name() { }
             }
@@ -234,6 +333,8 @@ mod error_tests {
                 #[allow (unused_mut)]
                 #[allow (unused_variables)] 
                 mod spoketest {
+                    #[allow(unused_imports)]
+                    use super::*;
                     compile_error!("expected a valid test case name in quotes following the dollars, but found `;` before any body was provided");
                 }
             "##,
@@ -252,6 +353,8 @@ mod error_tests {
                 #[allow (unused_mut)]
                 #[allow (unused_variables)] 
                 mod spoketest {
+                    #[allow(unused_imports)]
+                    use super::*;
                     compile_error!("reached end of input before reaching the end of the test definition");
                 }
             "##,
@@ -271,6 +374,8 @@ mod error_tests {
                 #[allow (unused_mut)]
                 #[allow (unused_variables)] 
                 mod spoketest {
+                    #[allow(unused_imports)]
+                    use super::*;
                     compile_error!("expected a valid test case name in quotes following the dollars, but found `hello`");
                 }
             "##,
@@ -290,6 +395,8 @@ mod error_tests {
                 #[allow (unused_mut)]
                 #[allow (unused_variables)] 
                 mod spoketest {
+                    #[allow(unused_imports)]
+                    use super::*;
                     compile_error!("expected a valid test case name in quotes following the dollars, but found `hello`");
                     compile_error!("reached end of input before reaching the end of the test definition");
                 }
@@ -309,7 +416,9 @@ mod error_tests {
                 #[cfg(test)]
                 #[allow (unused_mut)]
                 #[allow (unused_variables)] 
-                mod spoketest {
+                mod spoketest_hello_dcc6f1da {
+                    #[allow(unused_imports)]
+                    use super::*;
                     compile_error!("expected an assertion or test body after the name, but found `;`");
                 }
             "##,
@@ -329,6 +438,8 @@ mod error_tests {
                 #[allow (unused_mut)]
                 #[allow (unused_variables)] 
                 mod spoketest {
+                    #[allow(unused_imports)]
+                    use super::*;
                     compile_error!("expected a valid test case name in quotes following the dollars, but found `\"hello\"world`\nunmatched suffix detected, `world`, did you miss a space?");
                 }
             "###,
@@ -348,6 +459,8 @@ mod error_tests {
                 #[allow (unused_mut)]
                 #[allow (unused_variables)] 
                 mod spoketest {
+                    #[allow(unused_imports)]
+                    use super::*;
                     compile_error!("reached end of input before finding the test body for named test");
                 }
             "###,
@@ -366,7 +479,9 @@ mod error_tests {
                 #[cfg(test)]
                 #[allow (unused_mut)]
                 #[allow (unused_variables)] 
-                mod spoketest {
+                mod spoketest_hello_c4c6cc12 {
+                    #[allow(unused_imports)]
+                    use super::*;
                     compile_error!("reached end of input before finding details of the named assertion. Missing ; ?");
                 }
             "###,
@@ -386,6 +501,8 @@ mod error_tests {
                 #[allow (unused_mut)]
                 #[allow (unused_variables)] 
                 mod spoketest {
+                    #[allow(unused_imports)]
+                    use super::*;
                     compile_error!("expected a valid test case name in quotes following the dollars, but found an assertion 'eq' which isn't allowed as a top level test");
                 }
             "###,
@@ -405,6 +522,8 @@ mod error_tests {
                 #[allow (unused_mut)]
                 #[allow (unused_variables)] 
                 mod spoketest {
+                    #[allow(unused_imports)]
+                    use super::*;
                     compile_error!("expected a valid test case name in quotes following the dollars, but found a badly formatted assertion 'eq' which isn't allowed as a top level test and should be lowercase");
                 }
             "###,
@@ -27,13 +27,12 @@ impl<'a> Input<'a> {
         self.0
             .parse::<proc_macro2::TokenStream>()
             .inspect_err(|e| eprintln!("ERROR: {e}"))
-            .expect(
-                format!(
+            .unwrap_or_else(|_| {
+                panic!(
                     "the input string represents a valid input stream of tokens :: {}",
                     self.0
                 )
-                .as_str(),
-            )
+            })
     }
 }
 
@@ -44,10 +43,17 @@ impl<'a> Expected<'a> {
         self.0
             .parse::<proc_macro2::TokenStream>()
             .inspect_err(|e| eprintln!("ERROR: {e}"))
-            .expect(format!("the expected output to be valid rust :: {}", self.0).as_str())
+            .unwrap_or_else(|_| panic!("the expected output to be valid rust :: {}", self.0))
     }
 }
 
+pub(crate) fn parsing(input: Input) -> TokenStream {
+    // pin the module name so that every expectation shares the same suite structure
+    crate::spoke::generate_tests(
+        Input(format!("$config(module = spoketest); {}", input.0).as_str()).stream(),
+    )
+}
+
 #[track_caller]
 fn matches_stream(result: proc_macro2::TokenStream, expected: Expected) {
     assert_eq!(expected.stream().to_string(), result.to_string());
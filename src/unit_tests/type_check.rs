@@ -0,0 +1,220 @@
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Span;
+
+    #[allow(unused_imports)]
+    use super::*;
+
+    use crate::{type_check::support_module, unit_tests::testing_helpers::*};
+
+    struct SuiteStructure();
+    impl SurroundingString for SuiteStructure {
+        fn surround(input: &str) -> String {
+            format!(
+                "#[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]  mod spoketest {{ #[allow(unused_imports)] use super::*; {} }}",
+                input
+            )
+        }
+    }
+
+    // only `$type` needs a helper, `$impl` is checked with a const item
+    struct TypesSuiteStructure();
+    impl SurroundingString for TypesSuiteStructure {
+        fn surround(input: &str) -> String {
+            format!(
+                "#[cfg(test)] #[allow(unused_mut)] #[allow(unused_variables)]  mod spoketest {{ #[allow(unused_imports)] use super::*; {} {} }}",
+                support_module(Span::call_site()),
+                input
+            )
+        }
+    }
+
+    #[test]
+    fn impl_checks_the_type_against_the_trait_bounds() {
+        parsing(Input(
+            r##"
+                $"handle is send and sync" Handle $impl Send + Sync;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                const _: () = {
+                    #[allow(clippy::needless_maybe_sized)]
+                    const fn spoke_implements<T: ?Sized + Send + Sync>() {}
+                    spoke_implements::<Handle>();
+                };
+            "##,
+        ));
+    }
+
+    #[test]
+    fn impl_does_not_run_the_setup_around_it() {
+        parsing(Input(
+            r##"
+                $"a handle" {
+                    let handle = Handle::open();
+                    $after { handle.close(); }
+                    $"is send" Handle $impl Send;
+                }
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                const _: () = {
+                    #[allow(clippy::needless_maybe_sized)]
+                    const fn spoke_implements<T: ?Sized + Send>() {}
+                    spoke_implements::<Handle>();
+                };
+            "##,
+        ));
+    }
+
+    #[test]
+    fn impl_cannot_be_marked() {
+        parsing(Input(
+            r##"
+                $ignore "ignored" Handle $impl Send;
+                $pending "rc is send" std::rc::Rc<u8> $impl Send;
+                $"still parsed" true;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("a `$impl` assertion is checked at compile time so it can't be marked");
+                compile_error!("a `$impl` assertion is checked at compile time so it can't be marked");
+                #[test]
+                fn still_parsed() {
+                    assert!(true);
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn impl_accepts_lifetimes_and_generic_types() {
+        parsing(Input(
+            r##"
+                $"is static" Cache<String> $impl 'static + Default;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                const _: () = {
+                    #[allow(clippy::needless_maybe_sized)]
+                    const fn spoke_implements<T: ?Sized + 'static + Default>() {}
+                    spoke_implements::<Cache<String> >();
+                };
+            "##,
+        ));
+    }
+
+    #[test]
+    fn type_checks_the_expression_without_running_it() {
+        parsing(Input(
+            r##"
+                $"returns a u32" f() $type u32;
+            "##,
+        ))
+        .matches_inside::<TypesSuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn returns_a_u32() {
+                    {
+                        if false {
+                            spoke_types::check::<u32, _>(&(f()));
+                        }
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn type_checks_use_the_surrounding_code() {
+        parsing(Input(
+            r##"
+                $"a name" {
+                    let name = String::from("spoke");
+                    $"measures bytes" name.len() $type usize;
+                }
+            "##,
+        ))
+        .matches_inside::<TypesSuiteStructure>(Expected(
+            r##"
+                #[test]
+                fn a_name_measures_bytes() {
+                    let name = String::from("spoke");
+                    {
+                        if false {
+                            spoke_types::check::<usize, _>(&(name.len()));
+                        }
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn type_checks_can_await_in_async_tests() {
+        parsing(Input(
+            r##"
+                $async {
+                    $"loads a config" load().await $type Config;
+                }
+            "##,
+        ))
+        .matches_inside::<TypesSuiteStructure>(Expected(
+            r##"
+                #[tokio::test]
+                async fn loads_a_config() {
+                    {
+                        if false {
+                            spoke_types::check::<Config, _>(&(load().await));
+                        }
+                    }
+                }
+            "##,
+        ));
+    }
+
+    #[test]
+    fn type_checks_need_both_sides() {
+        parsing(Input(
+            r##"
+                $"no type" Handle $impl;
+                $"no bounds" $impl Send;
+                $"no expression" $type u32;
+                $"no expected type" f() $type;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("expected trait bounds following `$impl`");
+                compile_error!("no type found for the `$impl` assertion");
+                compile_error!("no code found for the expression of the `$type` assertion");
+                compile_error!("expected a type following `$type`");
+            "##,
+        ));
+    }
+
+    #[test]
+    fn type_checks_cannot_be_followed_by_further_dollars() {
+        parsing(Input(
+            r##"
+                $"impl" Handle $impl Send $onfail "not send";
+                $"type" f() $type u32 $onfail "not a u32";
+                $"still parsed" true;
+            "##,
+        ))
+        .matches_inside::<SuiteStructure>(Expected(
+            r##"
+                compile_error!("a `$impl` assertion is checked at compile time so it can't be followed by further dollars");
+                compile_error!("a `$type` assertion is checked at compile time so it can't be followed by further dollars");
+                #[test]
+                fn still_parsed() {
+                    assert!(true);
+                }
+            "##,
+        ));
+    }
+}
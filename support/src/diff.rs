@@ -0,0 +1,131 @@
+/// The number of unchanged lines shown either side of a change.
+const CONTEXT: usize = 3;
+
+/// Above this many pairs of lines the diff isn't worth the time to compute, the
+/// values are shown one after the other instead.
+const MAX_COMPARISONS: usize = 4_000_000;
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum Line<'a> {
+    Same(&'a str),
+    Left(&'a str),
+    Right(&'a str),
+    Skipped(usize),
+}
+
+impl Line<'_> {
+    pub(crate) fn render(&self, colour: bool) -> String {
+        let (prefix, text, code) = match self {
+            Line::Same(text) => (' ', *text, None),
+            Line::Left(text) => ('-', *text, Some("31")),
+            Line::Right(text) => ('+', *text, Some("32")),
+            Line::Skipped(1) => return "  ... 1 unchanged line".to_string(),
+            Line::Skipped(count) => return format!("  ... {} unchanged lines", count),
+        };
+
+        match (colour, code) {
+            (true, Some(code)) => format!("\x1b[{}m{} {}\x1b[0m", code, prefix, text),
+            _ => format!("{} {}", prefix, text),
+        }
+    }
+}
+
+/// Splits `Debug` output into lines, the escaped newlines of a string being
+/// treated as line breaks so that multi-line text is compared line by line.
+pub(crate) fn lines(debug: &str) -> Vec<String> {
+    let Some(text) = debug
+        .strip_prefix('"')
+        .and_then(|text| text.strip_suffix('"'))
+    else {
+        return debug.lines().map(str::to_string).collect();
+    };
+
+    let mut lines = vec![String::new()];
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        let line = lines.last_mut().expect("there is always a line");
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                chars.next();
+                lines.push(String::new());
+            }
+            ('\\', Some(escaped)) => {
+                chars.next();
+                line.push(c);
+                line.push(escaped);
+            }
+            (c, _) => line.push(c),
+        }
+    }
+    lines
+}
+
+/// A line based diff from the longest common subsequence of the lines, with long
+/// runs of unchanged lines skipped.
+pub(crate) fn diff<'a>(left: &'a [String], right: &'a [String]) -> Vec<Line<'a>> {
+    if left.len().saturating_mul(right.len()) > MAX_COMPARISONS {
+        return left
+            .iter()
+            .map(|line| Line::Left(line))
+            .chain(right.iter().map(|line| Line::Right(line)))
+            .collect();
+    }
+
+    // common[i][j] is the length of the longest common subsequence of left[i..] and right[j..]
+    let mut common = vec![vec![0usize; right.len() + 1]; left.len() + 1];
+    for i in (0..left.len()).rev() {
+        for j in (0..right.len()).rev() {
+            common[i][j] = if left[i] == right[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < left.len() || j < right.len() {
+        if i < left.len() && j < right.len() && left[i] == right[j] {
+            lines.push(Line::Same(&left[i]));
+            i += 1;
+            j += 1;
+        } else if j == right.len() || (i < left.len() && common[i + 1][j] >= common[i][j + 1]) {
+            lines.push(Line::Left(&left[i]));
+            i += 1;
+        } else {
+            lines.push(Line::Right(&right[j]));
+            j += 1;
+        }
+    }
+
+    skip_unchanged(lines)
+}
+
+fn skip_unchanged(lines: Vec<Line<'_>>) -> Vec<Line<'_>> {
+    let mut is_shown = vec![false; lines.len()];
+    for (index, line) in lines.iter().enumerate() {
+        if !matches!(line, Line::Same(_)) {
+            let around = index.saturating_sub(CONTEXT)..(index + CONTEXT + 1).min(lines.len());
+            is_shown[around].fill(true);
+        }
+    }
+
+    let mut output = Vec::new();
+    let mut skipped = 0;
+    for (line, is_shown) in lines.into_iter().zip(is_shown) {
+        if is_shown {
+            if skipped > 0 {
+                output.push(Line::Skipped(skipped));
+                skipped = 0;
+            }
+            output.push(line);
+        } else {
+            skipped += 1;
+        }
+    }
+    if skipped > 0 {
+        output.push(Line::Skipped(skipped));
+    }
+    output
+}
@@ -0,0 +1,55 @@
+//! Runtime support for the tests generated by [spoke](https://crates.io/crates/spoke).
+//!
+//! The generated tests call into this crate when spoke's `diff` feature is enabled,
+//! it isn't intended to be used directly.
+
+mod diff;
+
+use std::fmt::{Arguments, Debug};
+
+/// Checks that two values are equal, like `assert_eq!`, but when they differ across
+/// more than one line the failure shows a diff of their `Debug` output.
+#[track_caller]
+pub fn assert_eq<L, R>(left: &L, right: &R, message: Option<Arguments<'_>>)
+where
+    L: PartialEq<R> + Debug + ?Sized,
+    R: Debug + ?Sized,
+{
+    if left != right {
+        panic!("{}", report(left, right, message, colour_enabled()));
+    }
+}
+
+/// Colours are on unless turned off with `NO_COLOR`, see <https://no-color.org>.
+fn colour_enabled() -> bool {
+    std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+}
+
+fn report<L, R>(left: &L, right: &R, message: Option<Arguments<'_>>, colour: bool) -> String
+where
+    L: Debug + ?Sized,
+    R: Debug + ?Sized,
+{
+    let mut report = match message {
+        Some(message) => format!("assertion `left == right` failed: {}\n", message),
+        None => "assertion `left == right` failed\n".to_string(),
+    };
+
+    let left_lines = diff::lines(&format!("{:#?}", left));
+    let right_lines = diff::lines(&format!("{:#?}", right));
+
+    if left_lines.len() <= 1 && right_lines.len() <= 1 {
+        report.push_str(&format!("  left: {:?}\n right: {:?}", left, right));
+        return report;
+    }
+
+    report.push_str("diff (- left, + right):");
+    for line in diff::diff(&left_lines, &right_lines) {
+        report.push('\n');
+        report.push_str(&line.render(colour));
+    }
+    report
+}
+
+#[cfg(test)]
+mod unit_tests;
@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        diff::{Line, diff, lines},
+        report,
+    };
+
+    #[derive(Debug, PartialEq)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    fn owned(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn equal_values_pass() {
+        crate::assert_eq(&vec![1, 2], &[1, 2], None);
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion `left == right` failed: while testing\n")]
+    fn unequal_values_panic_with_the_message() {
+        crate::assert_eq(&1, &2, Some(format_args!("while {}", "testing")));
+    }
+
+    #[test]
+    fn single_line_values_are_reported_like_assert_eq() {
+        assert_eq!(
+            report(&1, &2, None, false),
+            "assertion `left == right` failed\n  left: 1\n right: 2"
+        );
+    }
+
+    #[test]
+    fn multi_line_values_are_reported_as_a_diff() {
+        assert_eq!(
+            report(&Point { x: 1, y: 2 }, &Point { x: 1, y: 3 }, None, false),
+            "assertion `left == right` failed\ndiff (- left, + right):\n  Point {\n      x: 1,\n-     y: 2,\n+     y: 3,\n  }"
+        );
+    }
+
+    #[test]
+    fn the_diff_is_coloured_when_enabled() {
+        assert_eq!(Line::Left("a").render(true), "\x1b[31m- a\x1b[0m");
+        assert_eq!(Line::Right("a").render(true), "\x1b[32m+ a\x1b[0m");
+        assert_eq!(Line::Same("a").render(true), "  a");
+    }
+
+    #[test]
+    fn a_string_is_split_on_its_escaped_newlines() {
+        assert_eq!(
+            lines(&format!("{:?}", "one\ntwo \"2\"\\n")),
+            owned(&["one", "two \\\"2\\\"\\\\n"])
+        );
+    }
+
+    #[test]
+    fn other_values_are_split_on_their_lines() {
+        assert_eq!(lines("[\n    1,\n]"), owned(&["[", "    1,", "]"]));
+    }
+
+    #[test]
+    fn unchanged_lines_far_from_a_change_are_skipped() {
+        let left = owned(&["a", "b", "c", "d", "e", "f", "g", "h", "i"]);
+        let right = owned(&["a", "b", "c", "d", "e", "f", "g", "h", "x"]);
+
+        assert_eq!(
+            diff(&left, &right),
+            [
+                Line::Skipped(5),
+                Line::Same("f"),
+                Line::Same("g"),
+                Line::Same("h"),
+                Line::Left("i"),
+                Line::Right("x"),
+            ]
+        );
+    }
+
+    #[test]
+    fn inserted_and_removed_lines_are_found() {
+        let left = owned(&["a", "b", "c"]);
+        let right = owned(&["a", "c", "d"]);
+
+        assert_eq!(
+            diff(&left, &right),
+            [
+                Line::Same("a"),
+                Line::Left("b"),
+                Line::Same("c"),
+                Line::Right("d"),
+            ]
+        );
+    }
+}
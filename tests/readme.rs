@@ -0,0 +1,17 @@
+// Examples from the README, compiled here so they keep working as the syntax changes.
+
+// The stack example changes the vector after creating it on purpose.
+#![allow(clippy::vec_init_then_push)]
+// assert2 isn't a dependency of spoke, so the generated assertions can't be built with it.
+#![cfg(not(feature = "assert2"))]
+
+spoke::test! {
+    $"a stack" {
+        let mut stack: Vec<i32> = Vec::new();
+        $"starts empty" stack.is_empty();
+        stack.push(1);
+        $"holds a pushed item" stack.len() $eq 1;
+        stack.pop();
+        $"is empty again after a pop" stack.is_empty();
+    }
+}